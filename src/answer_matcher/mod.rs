@@ -0,0 +1,212 @@
+use std::rc::Rc;
+
+use crate::per_blank::PerBlank;
+
+/// Decides if user answer for blank field is correct.
+///
+/// Implemented for closures, e.g. to accept any answer of given length:
+/// ```
+/// let matcher = |user_answer: &str, correct_answer: &str| {
+///     user_answer.chars().count() == correct_answer.chars().count()
+/// };
+/// let matchers = ratatui_inputs::answer_matcher::Matchers::new(matcher);
+/// ```
+pub trait AnswerMatcher {
+    fn matches(&self, user_answer: &str, correct_answer: &str) -> bool;
+}
+impl<F: Fn(&str, &str) -> bool> AnswerMatcher for F {
+    fn matches(&self, user_answer: &str, correct_answer: &str) -> bool {
+        self(user_answer, correct_answer)
+    }
+}
+
+/// Returns `true` if `user_answer` matches any of `accepted` answers.
+pub(crate) fn matches_any(
+    matcher: &dyn AnswerMatcher,
+    user_answer: &str,
+    accepted: &[String],
+) -> bool {
+    accepted.iter().any(|x| matcher.matches(user_answer, x))
+}
+
+/// Answers are equal after trimming.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Exact;
+impl AnswerMatcher for Exact {
+    fn matches(&self, user_answer: &str, correct_answer: &str) -> bool {
+        user_answer.trim() == correct_answer.trim()
+    }
+}
+
+/// Answers are equal after trimming, ignoring case.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CaseInsensitive;
+impl AnswerMatcher for CaseInsensitive {
+    fn matches(&self, user_answer: &str, correct_answer: &str) -> bool {
+        user_answer.trim().to_lowercase() == correct_answer.trim().to_lowercase()
+    }
+}
+
+/// Answers are equal ignoring punctuation and amount of whitespace between words.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IgnorePunctuation;
+impl AnswerMatcher for IgnorePunctuation {
+    fn matches(&self, user_answer: &str, correct_answer: &str) -> bool {
+        let normalize = |s: &str| {
+            s.chars()
+                .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+                .collect::<String>()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        normalize(user_answer) == normalize(correct_answer)
+    }
+}
+
+/// Answers are canonically equivalent after trimming, e.g. `é` written as one char and as `e`
+/// followed by combining acute accent.
+#[cfg(feature = "unicode-normalization")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unicode {
+    /// Ignore diacritical marks, so `é` matches `e`.
+    pub fold_diacritics: bool,
+}
+#[cfg(feature = "unicode-normalization")]
+impl AnswerMatcher for Unicode {
+    fn matches(&self, user_answer: &str, correct_answer: &str) -> bool {
+        use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+        let normalize = |s: &str| {
+            if self.fold_diacritics {
+                s.trim()
+                    .nfd()
+                    .filter(|&c| !is_combining_mark(c))
+                    .collect::<String>()
+            } else {
+                s.trim().nfc().collect()
+            }
+        };
+        normalize(user_answer) == normalize(correct_answer)
+    }
+}
+
+/// Correct answer is regular expression which must match whole trimmed user answer.
+///
+/// Invalid expressions match nothing.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Regex {
+    pub case_insensitive: bool,
+}
+#[cfg(feature = "regex")]
+impl AnswerMatcher for Regex {
+    fn matches(&self, user_answer: &str, correct_answer: &str) -> bool {
+        regex::RegexBuilder::new(&format!("^(?:{correct_answer})$"))
+            .case_insensitive(self.case_insensitive)
+            .build()
+            .is_ok_and(|x| x.is_match(user_answer.trim()))
+    }
+}
+
+/// Answers differ in at most `max_distance` inserted, removed or replaced chars.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EditDistance {
+    pub max_distance: usize,
+}
+impl AnswerMatcher for EditDistance {
+    fn matches(&self, user_answer: &str, correct_answer: &str) -> bool {
+        edit_distance(user_answer.trim(), correct_answer.trim()) <= self.max_distance
+    }
+}
+
+/// Levenshtein distance in chars.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let replace = prev_row[j] + usize::from(a != *b);
+            row.push(replace.min(prev_row[j + 1] + 1).min(row[j] + 1));
+        }
+        prev_row = row;
+    }
+    prev_row[b.len()]
+}
+
+/// [`AnswerMatcher`] for whole deck with overrides for single blank fields.
+#[derive(Debug, Clone)]
+pub struct Matchers(PerBlank<dyn AnswerMatcher>);
+impl Default for Matchers {
+    fn default() -> Self {
+        Self::new(Exact)
+    }
+}
+impl Matchers {
+    pub fn new(default: impl AnswerMatcher + 'static) -> Self {
+        Self(PerBlank::new(Rc::new(default)))
+    }
+    /// Use `matcher` for blank field indexed as in [`Options::blanks`](crate::Options::blanks).
+    pub fn with_blank(
+        self,
+        block: usize,
+        blank: usize,
+        matcher: impl AnswerMatcher + 'static,
+    ) -> Self {
+        Self(self.0.with_blank(block, blank, Rc::new(matcher)))
+    }
+    pub fn get(&self, block: usize, blank: usize) -> &dyn AnswerMatcher {
+        self.0.get(block, blank).expect("matchers have default")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_counts_chars() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("été", "ete"), 2);
+        assert!(EditDistance { max_distance: 1 }.matches(" colour ", "color"));
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn unicode_is_canonical_equivalence() {
+        let matcher = Unicode::default();
+        assert!(matcher.matches("e\u{301}t\u{e9}", "\u{e9}te\u{301}"));
+        assert!(matcher.matches("\u{438}\u{306}", "\u{439}"));
+        assert!(matcher.matches("q\u{307}\u{323}", "q\u{323}\u{307}"));
+        assert!(!matcher.matches("ete", "été"));
+        assert!(!matcher.matches("ﬁ", "fi"));
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn unicode_folds_diacritics() {
+        let matcher = Unicode {
+            fold_diacritics: true,
+        };
+        assert!(matcher.matches("ete", "été"));
+        assert!(matcher.matches("\u{438}", "\u{439}"));
+        assert!(matcher.matches("Ångström", "Angstrom"));
+        assert!(!matcher.matches("ete", "ote"));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_matches_whole_answer() {
+        let matcher = Regex::default();
+        assert!(matcher.matches(" 42 ", r"\d+"));
+        assert!(!matcher.matches("42a", r"\d+"));
+        assert!(matcher.matches("ab", "a|ab"));
+        assert!(!matcher.matches("Colour", "colou?r"));
+        assert!(!matcher.matches("anything", "("));
+        let matcher = Regex {
+            case_insensitive: true,
+        };
+        assert!(matcher.matches("Colour", "colou?r"));
+    }
+}
@@ -1,12 +1,51 @@
+use std::time::Instant;
+
 use super::ResultKind;
+use crate::{
+    completion::Completer,
+    compose::compose,
+    labels::fill,
+    timer::{self, TimedEvent},
+    validator::Validator,
+    BlankOptions, ComposeOptions, HintKind, HintOptions, Labels,
+};
 use crossterm::event::{KeyCode, KeyEventKind};
 
 #[derive(Default, Clone)]
 #[readonly::make]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlankField {
     pub text: Vec<char>,
     pub cursor: usize,
+    /// Number of hints that revealed something new.
+    pub hints: usize,
+    /// Hint displayed next to field.
+    pub hint: Option<String>,
+    /// Number of changes of text made by user.
+    pub edits: usize,
+    pub options: BlankOptions,
+    /// Error of last validation, cleared by editing.
+    pub error: Option<String>,
+    /// Suggestions of [`Completer`] for current text.
+    pub suggestions: Vec<String>,
+    /// Index of focused suggestion.
+    pub suggestion: Option<usize>,
+    /// Chars typed after compose key, `None` if not composing.
+    pub composing: Option<String>,
+}
+
+/// Maximal number of suggestions shown.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Whether `c` can be typed at position of `m` in [`BlankOptions::mask`].
+fn fits_mask(m: char, c: char) -> bool {
+    match m {
+        '#' => c.is_ascii_digit(),
+        'a' => c.is_alphabetic(),
+        '*' => true,
+        _ => c == m,
+    }
 }
 
 enum Event {
@@ -21,19 +60,85 @@ enum Event {
     Redraw,
     AddString(String),
     Cancel,
+    Hint,
+    TimedOut,
+    NextSuggestion,
+    PrevSuggestion,
+    Compose,
 }
 
 impl BlankField {
+    /// Creates field with `text` and cursor at the end.
+    pub fn new(text: &str) -> Self {
+        let text = text.chars().collect::<Vec<_>>();
+        Self {
+            cursor: text.len(),
+            text,
+            ..Default::default()
+        }
+    }
+    pub fn set_options(&mut self, options: BlankOptions) {
+        self.options = options;
+    }
     pub fn text(&self) -> String {
         self.text.iter().collect()
     }
+    /// Text as displayed with [`BlankOptions::echo`].
+    pub fn displayed_text(&self) -> String {
+        self.options.echo.apply(&self.text())
+    }
     pub fn is_empty(&self) -> bool {
         self.text().trim().is_empty()
     }
     fn add_char(&mut self, c: char) {
+        let mut literals = Vec::new();
+        if let Some(mask) = &self.options.mask {
+            let mask = mask.chars().collect::<Vec<_>>();
+            loop {
+                match mask.get(self.cursor + literals.len()) {
+                    Some(&m) if fits_mask(m, c) => break,
+                    Some(&m) if !"#a*".contains(m) => literals.push(m),
+                    _ => return,
+                }
+            }
+        }
+        if self
+            .options
+            .max_length
+            .is_some_and(|x| self.text.len() + literals.len() >= x)
+        {
+            return;
+        }
+        for literal in literals {
+            self.text.insert(self.cursor, literal);
+            self.move_cursor_right();
+        }
         self.text.insert(self.cursor, c);
         self.move_cursor_right();
     }
+    /// Checks [`mask`](BlankOptions::mask) and `validator`, setting [`error`](Self::error).
+    ///
+    /// # Returns
+    /// `true` if field is empty or valid
+    pub fn validate(&mut self, validator: Option<&dyn Validator>, labels: &Labels) -> bool {
+        self.error = None;
+        if self.is_empty() {
+            return true;
+        }
+        let text = self.text();
+        if let Some(mask) = &self.options.mask {
+            if mask.chars().count() != self.text.len()
+                || !mask.chars().zip(&self.text).all(|(m, &c)| fits_mask(m, c))
+            {
+                self.error = Some(fill(&labels.mask_incomplete, &[mask]));
+                return false;
+            }
+        }
+        if let Some(Err(error)) = validator.map(|x| x.validate(&text, labels)) {
+            self.error = Some(error);
+        }
+        self.error.is_none()
+    }
     fn remove_current_char(&mut self) {
         if self.cursor != 0 {
             self.text.remove(self.cursor - 1);
@@ -53,14 +158,65 @@ impl BlankField {
         self.cursor += 1;
         self.cursor = self.cursor.clamp(0, self.text.len());
     }
+    /// # Returns
+    /// `true` if hint revealed something new
+    fn show_hint(&mut self, kind: HintKind, correct_answer: &str) -> bool {
+        let correct_answer = correct_answer.trim();
+        let words = |f: fn(&str) -> String| {
+            correct_answer
+                .split_whitespace()
+                .map(f)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        match kind {
+            HintKind::NextLetter => {
+                let correct_answer = correct_answer.chars().collect::<Vec<_>>();
+                if self.text == correct_answer {
+                    return false;
+                }
+                let common = self
+                    .text
+                    .iter()
+                    .zip(&correct_answer)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                self.text = correct_answer[..(common + 1).min(correct_answer.len())].to_vec();
+                self.cursor = self.text.len();
+                true
+            }
+            HintKind::FirstLetters => self.set_hint(words(|word| {
+                word.chars()
+                    .enumerate()
+                    .map(|(i, c)| if i == 0 { c } else { '_' })
+                    .collect()
+            })),
+            HintKind::Length => self.set_hint(words(|word| "_".repeat(word.chars().count()))),
+        }
+    }
+    fn set_hint(&mut self, hint: String) -> bool {
+        let is_new = self.hint.as_ref() != Some(&hint);
+        self.hint = Some(hint);
+        is_new
+    }
     #[allow(clippy::too_many_lines)]
-    fn get_event() -> std::io::Result<Event> {
+    fn get_event(
+        hint: Option<&HintOptions>,
+        compose: Option<&ComposeOptions>,
+        deadline: Option<Instant>,
+    ) -> std::io::Result<Event> {
         Ok({
             loop {
-                if let Some(x) = match crossterm::event::read()? {
-                    crossterm::event::Event::Key(k) => {
-                        if k.kind == KeyEventKind::Press {
+                if let Some(x) = match timer::read(deadline)? {
+                    TimedEvent::TimedOut => Some(Event::TimedOut),
+                    TimedEvent::Tick | TimedEvent::Event(crossterm::event::Event::Resize(_, _)) => {
+                        Some(Event::Redraw)
+                    }
+                    TimedEvent::Event(crossterm::event::Event::Key(k)) => {
+                        if matches!(k.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
                             match k.code {
+                                _ if hint.is_some_and(|x| x.matches(&k)) => Some(Event::Hint),
+                                _ if compose.is_some_and(|x| x.matches(&k)) => Some(Event::Compose),
                                 KeyCode::Backspace => Some(Event::RemoveCurrentChar),
                                 KeyCode::Enter => Some(Event::Finish),
                                 KeyCode::Left => Some(Event::MoveCursorLeft),
@@ -68,6 +224,8 @@ impl BlankField {
                                 KeyCode::Tab => Some(Event::NextField),
                                 KeyCode::BackTab => Some(Event::PrevField),
                                 KeyCode::Delete => Some(Event::RemoveNextChar),
+                                KeyCode::Down => Some(Event::NextSuggestion),
+                                KeyCode::Up => Some(Event::PrevSuggestion),
                                 KeyCode::Char(c) => Some(Event::AddChar(c)),
                                 KeyCode::Esc => Some(Event::Cancel),
                                 _ => None,
@@ -76,49 +234,285 @@ impl BlankField {
                             None
                         }
                     }
-                    crossterm::event::Event::Paste(s) => Some(Event::AddString(s)),
-                    crossterm::event::Event::Resize(_, _) => Some(Event::Redraw),
-                    _ => None,
+                    TimedEvent::Event(crossterm::event::Event::Paste(s)) => {
+                        Some(Event::AddString(s))
+                    }
+                    TimedEvent::Event(_) => None,
                 } {
                     break x;
                 }
             }
         })
     }
-    fn handle_event(&mut self, event: Event) -> Option<Event> {
+    /// Focused suggestion or first one if `or_first`, `None` if it's same as text.
+    fn chosen_suggestion(&self, or_first: bool) -> Option<&String> {
+        let i = self.suggestion.or(or_first.then_some(0))?;
+        self.suggestions.get(i).filter(|x| **x != self.text())
+    }
+    fn accept_suggestion(&mut self, or_first: bool) -> bool {
+        let Some(suggestion) = self.chosen_suggestion(or_first) else {
+            return false;
+        };
+        self.text = suggestion.chars().collect();
+        self.cursor = self.text.len();
+        self.edits += 1;
+        self.error = None;
+        self.close_suggestions();
+        true
+    }
+    fn close_suggestions(&mut self) {
+        self.suggestions.clear();
+        self.suggestion = None;
+    }
+    fn update_suggestions(&mut self, completer: &dyn Completer) {
+        self.suggestion = None;
+        self.suggestions = if self.is_empty() {
+            Vec::new()
+        } else {
+            let mut suggestions = completer.complete(&self.text());
+            suggestions.truncate(MAX_SUGGESTIONS);
+            suggestions
+        };
+    }
+    /// Feeds `event` to started composition.
+    ///
+    /// # Returns
+    /// event to handle as usual, `None` if it was consumed
+    fn compose_event(&mut self, event: Event, compose: Option<&ComposeOptions>) -> Option<Event> {
+        let (Some(mut pending), Some(compose)) = (self.composing.take(), compose) else {
+            return Some(event);
+        };
         match event {
+            Event::AddChar(c) => {
+                let Some(first) = pending.chars().next() else {
+                    self.composing = Some(c.to_string());
+                    return None;
+                };
+                match self::compose(first, c, compose) {
+                    Some(composed) => Some(Event::AddChar(composed)),
+                    None => {
+                        pending.push(c);
+                        Some(Event::AddString(pending))
+                    }
+                }
+            }
+            Event::Redraw => {
+                self.composing = Some(pending);
+                None
+            }
+            Event::Cancel | Event::RemoveCurrentChar => None,
+            event => Some(event),
+        }
+    }
+    fn handle_event(
+        &mut self,
+        event: Event,
+        hint: Option<(&HintOptions, &str)>,
+        completer: Option<&dyn Completer>,
+        compose: Option<&ComposeOptions>,
+    ) -> Option<Event> {
+        let event = self.compose_event(event, compose)?;
+        let is_edit = matches!(
+            event,
+            Event::AddChar(_)
+                | Event::RemoveCurrentChar
+                | Event::RemoveNextChar
+                | Event::AddString(_)
+        );
+        let len = self.text.len();
+        match event {
+            Event::Hint => {
+                if let Some((hint, correct_answer)) = hint {
+                    if self.show_hint(hint.kind, correct_answer) {
+                        self.hints += 1;
+                    }
+                }
+            }
             Event::AddChar(c) => self.add_char(c),
             Event::RemoveCurrentChar => self.remove_current_char(),
             Event::RemoveNextChar => self.remove_next_char(),
             Event::MoveCursorLeft => self.move_cursor_left(),
             Event::MoveCursorRight => self.move_cursor_right(),
+            Event::Finish if self.accept_suggestion(false) => (),
+            Event::NextField if self.accept_suggestion(true) => (),
+            Event::Cancel if !self.suggestions.is_empty() => self.close_suggestions(),
             Event::Finish => return Some(event),
             Event::NextField => return Some(event),
             Event::PrevField => return Some(event),
             Event::Redraw => (),
             Event::AddString(s) => s.chars().for_each(|c| self.add_char(c)),
             Event::Cancel => return Some(event),
+            Event::TimedOut => return Some(event),
+            Event::NextSuggestion if !self.suggestions.is_empty() => {
+                let last = self.suggestions.len() - 1;
+                self.suggestion = Some(self.suggestion.map_or(0, |x| (x + 1).min(last)));
+            }
+            Event::PrevSuggestion => {
+                self.suggestion = self.suggestion.and_then(|x| x.checked_sub(1))
+            }
+            Event::NextSuggestion => (),
+            Event::Compose => self.composing = Some(String::new()),
+        }
+        if is_edit && self.text.len() != len {
+            self.edits += 1;
+            self.error = None;
+            if let Some(completer) = completer {
+                self.update_suggestions(completer);
+            }
         }
         None
     }
 }
 
 impl BlankField {
+    /// `hint` is used with correct answer to show hints, `completer` suggests answers and
+    /// `compose` enables typing accented chars.
+    ///
+    /// Finishes with [`ResultKind::TimedOut`] at `deadline`.
     pub fn get_input(
         &mut self,
+        hint: Option<(&HintOptions, &str)>,
+        completer: Option<&dyn Completer>,
+        compose: Option<&ComposeOptions>,
+        deadline: Option<Instant>,
         render: &mut impl FnMut(&Self) -> std::io::Result<()>,
     ) -> std::io::Result<ResultKind> {
         loop {
             render(self)?;
-            if let Some(x) = self.handle_event(Self::get_event()?) {
+            let event = Self::get_event(hint.map(|(x, _)| x), compose, deadline)?;
+            if let Some(x) = self.handle_event(event, hint, completer, compose) {
+                self.close_suggestions();
                 match x {
                     Event::Finish => return Ok(ResultKind::Ok),
                     Event::NextField => return Ok(ResultKind::NextBlock),
                     Event::PrevField => return Ok(ResultKind::PrevBlock),
                     Event::Cancel => return Ok(ResultKind::Canceled),
+                    Event::TimedOut => return Ok(ResultKind::TimedOut),
                     _ => unreachable!(),
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn masked(mask: &str) -> BlankField {
+        let mut blank_field = BlankField::default();
+        blank_field.set_options(BlankOptions {
+            mask: Some(mask.to_owned()),
+            ..Default::default()
+        });
+        blank_field
+    }
+    fn type_text(blank_field: &mut BlankField, text: &str) {
+        text.chars().for_each(|c| blank_field.add_char(c));
+    }
+
+    #[test]
+    fn mask_inserts_literals() {
+        let mut blank_field = masked("##.##");
+        type_text(&mut blank_field, "123");
+        assert_eq!(blank_field.text(), "12.3");
+        assert_eq!(blank_field.cursor, 4);
+        let mut blank_field = masked("(###)");
+        type_text(&mut blank_field, "(1");
+        assert_eq!(blank_field.text(), "(1");
+    }
+
+    #[test]
+    fn mask_rejects_chars() {
+        let mut blank_field = masked("##.aa");
+        type_text(&mut blank_field, "1x");
+        assert_eq!(blank_field.text(), "1");
+        type_text(&mut blank_field, "2.3");
+        assert_eq!(blank_field.text(), "12.");
+        type_text(&mut blank_field, "éab");
+        assert_eq!(blank_field.text(), "12.éa");
+    }
+
+    #[test]
+    fn max_length_counts_mask_literals() {
+        let mut blank_field = masked("##-##");
+        blank_field.options.max_length = Some(3);
+        type_text(&mut blank_field, "123");
+        assert_eq!(blank_field.text(), "12");
+        blank_field.options.max_length = Some(4);
+        type_text(&mut blank_field, "34");
+        assert_eq!(blank_field.text(), "12-3");
+    }
+
+    #[test]
+    fn validate_reports_incomplete_mask() {
+        let labels = Labels::default();
+        let mut blank_field = masked("##.##");
+        assert!(blank_field.validate(None, &labels));
+        type_text(&mut blank_field, "123");
+        assert!(!blank_field.validate(None, &labels));
+        assert_eq!(
+            blank_field.error.as_deref(),
+            Some("Enter text in format ##.##.")
+        );
+        type_text(&mut blank_field, "4");
+        assert!(blank_field.validate(None, &labels));
+        assert_eq!(blank_field.error, None);
+        let validator = |text: &str| match text {
+            "12.34" => Err("taken".to_owned()),
+            _ => Ok(()),
+        };
+        assert!(!blank_field.validate(Some(&validator), &labels));
+        assert_eq!(blank_field.error.as_deref(), Some("taken"));
+    }
+
+    #[test]
+    fn next_letter_hint() {
+        let mut blank_field = BlankField::default();
+        assert!(blank_field.show_hint(HintKind::NextLetter, " colour "));
+        assert_eq!(blank_field.text(), "c");
+        let mut blank_field = BlankField::new("cop");
+        assert!(blank_field.show_hint(HintKind::NextLetter, "colour"));
+        assert_eq!(blank_field.text(), "col");
+        assert_eq!(blank_field.cursor, 3);
+        let mut blank_field = BlankField::new("colours");
+        assert!(blank_field.show_hint(HintKind::NextLetter, "colour"));
+        assert_eq!(blank_field.text(), "colour");
+        assert!(!blank_field.show_hint(HintKind::NextLetter, "colour"));
+        assert_eq!(blank_field.text(), "colour");
+    }
+
+    #[test]
+    fn word_hints() {
+        let mut blank_field = BlankField::new("x");
+        assert!(blank_field.show_hint(HintKind::FirstLetters, " big  été "));
+        assert_eq!(blank_field.hint.as_deref(), Some("b__ é__"));
+        assert!(blank_field.show_hint(HintKind::Length, " big  été "));
+        assert_eq!(blank_field.hint.as_deref(), Some("___ ___"));
+        assert_eq!(blank_field.text(), "x");
+    }
+
+    #[test]
+    fn hint_counted_if_new() {
+        let hint = HintOptions {
+            kind: HintKind::FirstLetters,
+            ..Default::default()
+        };
+        let mut blank_field = BlankField::default();
+        for _ in 0..2 {
+            blank_field.handle_event(Event::Hint, Some((&hint, "cat")), None, None);
+        }
+        assert_eq!(blank_field.hints, 1);
+        let hint = HintOptions {
+            kind: HintKind::NextLetter,
+            ..Default::default()
+        };
+        for _ in 0..4 {
+            blank_field.handle_event(Event::Hint, Some((&hint, "cat")), None, None);
+        }
+        assert_eq!(blank_field.text(), "cat");
+        assert_eq!(blank_field.hints, 4);
+        blank_field.handle_event(Event::Hint, None, None, None);
+        assert_eq!(blank_field.hints, 4);
+    }
+}
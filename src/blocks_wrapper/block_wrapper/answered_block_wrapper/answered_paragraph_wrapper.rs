@@ -1,45 +1,100 @@
-use ratatui::{
-    style::Stylize,
-    text::{Line, Span},
+use std::borrow::Cow;
+
+use ratatui::text::{Line, Span};
+
+use crate::{
+    answer_matcher::{edit_distance, matches_any, AnswerMatcher, Exact},
+    diff::{diff, DiffKind},
+    labels,
+    theme::{CORRECTION_ARROW, CORRECT_MARKER, INCORRECT_MARKER},
+    AnswerDiff, Options, Theme,
 };
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnsweredParagraphWrapper(Vec<AnsweredParagraphItem>);
 impl From<Vec<s_text_input_f::ParagraphItemAnswered>> for AnsweredParagraphWrapper {
     fn from(value: Vec<s_text_input_f::ParagraphItemAnswered>) -> Self {
-        Self(value.into_iter().map(|x| x.into()).collect())
+        Self(
+            value
+                .into_iter()
+                .map(|x| match x {
+                    s_text_input_f::ParagraphItemAnswered::Text(s) => {
+                        AnsweredParagraphItem::Text(s)
+                    }
+                    s_text_input_f::ParagraphItemAnswered::Answer {
+                        user_answer,
+                        correct_answer,
+                    } => AnsweredParagraphItem::new(user_answer, vec![correct_answer], &Exact),
+                })
+                .collect(),
+        )
     }
 }
 impl AnsweredParagraphWrapper {
-    pub fn as_line(&self) -> Line {
-        self.0.iter().flat_map(|x| x.as_spans()).collect()
+    /// `correct_answer` contains accepted answers for each blank field.
+    ///
+    /// `matcher` gets index of blank field.
+    pub fn new<'a>(
+        paragraph: s_text_input_f::Paragraph,
+        user_answer: Vec<String>,
+        correct_answer: Vec<Vec<String>>,
+        matcher: impl Fn(usize) -> &'a dyn AnswerMatcher,
+    ) -> Self {
+        let mut blank = 0;
+        Self(
+            paragraph
+                .into_iter()
+                .map(|x| match x {
+                    s_text_input_f::ParagraphItem::Text(s) => AnsweredParagraphItem::Text(s),
+                    s_text_input_f::ParagraphItem::Placeholder => {
+                        let item = AnsweredParagraphItem::new(
+                            user_answer.get(blank).cloned().unwrap_or_default(),
+                            correct_answer.get(blank).cloned().unwrap_or_default(),
+                            matcher(blank),
+                        );
+                        blank += 1;
+                        item
+                    }
+                })
+                .collect(),
+        )
+    }
+    pub fn user_answer(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .filter_map(|x| match x {
+                AnsweredParagraphItem::Text(_) => None,
+                AnsweredParagraphItem::Answer { user_answer, .. } => Some(user_answer.clone()),
+            })
+            .collect()
+    }
+    pub fn as_line(&self, options: &Options) -> Line<'_> {
+        self.0.iter().flat_map(|x| x.as_spans(options)).collect()
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum AnsweredParagraphItem {
     Text(String),
     Answer {
         user_answer: String,
-        correct_answer: String,
+        /// Accepted answers, first one is displayed as correct.
+        correct_answer: Vec<String>,
+        is_correct: bool,
     },
 }
-impl From<s_text_input_f::ParagraphItemAnswered> for AnsweredParagraphItem {
-    fn from(value: s_text_input_f::ParagraphItemAnswered) -> Self {
-        match value {
-            s_text_input_f::ParagraphItemAnswered::Text(s) => AnsweredParagraphItem::Text(s),
-            s_text_input_f::ParagraphItemAnswered::Answer {
-                user_answer,
-                correct_answer,
-            } => AnsweredParagraphItem::Answer {
-                user_answer,
-                correct_answer,
-            },
+impl AnsweredParagraphItem {
+    fn new(user_answer: String, correct_answer: Vec<String>, matcher: &dyn AnswerMatcher) -> Self {
+        let is_correct = matches_any(matcher, &user_answer, &correct_answer);
+        Self::Answer {
+            user_answer,
+            correct_answer,
+            is_correct,
         }
     }
-}
-impl AnsweredParagraphItem {
-    pub fn as_spans(&self) -> Vec<Span> {
+    pub fn as_spans(&self, options: &Options) -> Vec<Span<'_>> {
         match self {
             AnsweredParagraphItem::Text(s) => {
                 vec![Span::raw(s)]
@@ -47,26 +102,142 @@ impl AnsweredParagraphItem {
             AnsweredParagraphItem::Answer {
                 user_answer,
                 correct_answer,
+                is_correct,
             } => {
-                let user_answer = if user_answer.trim().is_empty() {
-                    "<empty>"
+                let (canonical, also_accepted) = correct_answer
+                    .split_first()
+                    .map_or(("", &[][..]), |(x, xs)| (x.as_str(), xs));
+                let canonical = if canonical.trim().is_empty() {
+                    Cow::Owned(options.labels.empty.clone())
                 } else {
-                    user_answer
+                    Cow::Borrowed(canonical)
                 };
-                let correct_answer = if correct_answer.trim().is_empty() {
-                    "<empty>"
+                let theme = &options.theme;
+                let (correct_marker, incorrect_marker, arrow) = if theme.markers {
+                    (CORRECT_MARKER, INCORRECT_MARKER, CORRECTION_ARROW)
                 } else {
-                    correct_answer
+                    ("", "", "")
                 };
-                if user_answer.trim() == correct_answer.trim() {
-                    vec![Span::raw(correct_answer).green()]
-                } else {
+                let mut spans = if *is_correct {
                     vec![
-                        Span::raw(user_answer).red().crossed_out(),
-                        Span::raw(correct_answer).yellow(),
+                        Span::raw(correct_marker),
+                        Span::styled(canonical, theme.correct),
                     ]
+                } else if user_answer.trim().is_empty() {
+                    vec![
+                        Span::raw(incorrect_marker),
+                        Span::styled(options.labels.not_answered_blank.clone(), theme.empty_blank),
+                        Span::raw(arrow),
+                        Span::styled(canonical, theme.correction),
+                    ]
+                } else {
+                    let mut spans = vec![Span::raw(incorrect_marker)];
+                    spans.extend(match options.answer_diff {
+                        AnswerDiff::Whole => vec![
+                            Span::styled(user_answer, theme.incorrect_text),
+                            Span::raw(arrow),
+                            Span::styled(canonical, theme.correction),
+                        ],
+                        AnswerDiff::Chars => {
+                            let closest = closest(user_answer, correct_answer);
+                            let user_answer = user_answer.trim().chars().collect::<Vec<_>>();
+                            let closest = closest.trim().chars().collect::<Vec<_>>();
+                            diff_spans(&user_answer, &closest, theme, |x| x.iter().collect())
+                        }
+                        AnswerDiff::Words => {
+                            let closest = closest(user_answer, correct_answer);
+                            let user_answer = words(user_answer.trim());
+                            let closest = words(closest.trim());
+                            diff_spans(&user_answer, &closest, theme, |x| x.concat())
+                        }
+                    });
+                    spans
+                };
+                if !*is_correct && !also_accepted.is_empty() {
+                    spans.push(Span::styled(
+                        labels::fill(&options.labels.also_accepted, &[&also_accepted.join(", ")]),
+                        theme.dimmed,
+                    ));
                 }
+                spans
             }
         }
     }
 }
+
+/// Accepted answer with smallest edit distance to `user_answer`.
+fn closest<'a>(user_answer: &str, accepted: &'a [String]) -> &'a str {
+    accepted
+        .iter()
+        .min_by_key(|x| edit_distance(user_answer.trim(), x.trim()))
+        .map_or("", String::as_str)
+}
+
+/// Splits `s` to words and whitespace between them.
+fn words(s: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, c) in s.char_indices().skip(1) {
+        let prev = s[..i].chars().next_back().unwrap();
+        if prev.is_whitespace() != c.is_whitespace() {
+            words.push(&s[start..i]);
+            start = i;
+        }
+    }
+    if start < s.len() {
+        words.push(&s[start..]);
+    }
+    words
+}
+
+fn diff_spans<'a, T: PartialEq>(
+    user_answer: &[T],
+    correct_answer: &[T],
+    theme: &Theme,
+    to_string: impl Fn(&[T]) -> String,
+) -> Vec<Span<'a>> {
+    diff(user_answer, correct_answer)
+        .into_iter()
+        .map(|(kind, range)| match kind {
+            DiffKind::Equal => Span::styled(to_string(&user_answer[range]), theme.correct),
+            DiffKind::Removed => Span::styled(to_string(&user_answer[range]), theme.incorrect_text),
+            DiffKind::Added => Span::styled(to_string(&correct_answer[range]), theme.missing),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use s_text_input_f::ParagraphItem;
+
+    use super::*;
+
+    #[test]
+    fn partial_answers_keep_every_blank() {
+        let paragraph = vec![
+            ParagraphItem::Placeholder,
+            ParagraphItem::Text(" and ".to_owned()),
+            ParagraphItem::Placeholder,
+        ];
+        let answered = AnsweredParagraphWrapper::new(
+            paragraph,
+            vec!["a".to_owned()],
+            vec![vec!["a".to_owned()]],
+            |_| &Exact,
+        );
+        assert_eq!(answered.user_answer(), ["a", ""]);
+        let line = answered.as_line(&Options::default()).to_string();
+        assert!(line.ends_with(&Options::default().labels.empty), "{line}");
+    }
+
+    #[test]
+    fn words_keep_whitespace() {
+        assert!(words("").is_empty());
+        assert_eq!(words("one"), ["one"]);
+        assert_eq!(words(" a  bc "), [" ", "a", "  ", "bc", " "]);
+        assert_eq!(
+            words("été\u{a0}\u{3000}ça\tva"),
+            ["été", "\u{a0}\u{3000}", "ça", "\t", "va"]
+        );
+    }
+}
@@ -1,13 +1,32 @@
-use crate::ResultKind;
+use std::time::Instant;
+
+use crate::{
+    answer_matcher::AnswerMatcher, completion::Popup, validator::Validator, BlankOptions,
+    CursorPosition, Labels, Options, ResultKind,
+};
 use ratatui::text::Line;
 
+/// Which input is focused when block gets focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Entry {
+    First,
+    Last,
+    /// Keep input selected before(e.g. with `select_placeholder`).
+    Current,
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlockWrapper {
     Order,
     AnyOf,
     OneOf(one_of_wrapper::OneOfWrapper),
     Paragraph(paragraph_wrapper::ParagraphWrapper),
     Answered(answered_block_wrapper::AnsweredBlockWrapper),
+    /// Block answered during input, see [`Options::immediate_feedback`], and hints used for
+    /// each blank field.
+    Locked(answered_block_wrapper::AnsweredBlockWrapper, Vec<usize>),
 }
 impl From<s_text_input_f::Block> for BlockWrapper {
     fn from(value: s_text_input_f::Block) -> Self {
@@ -35,42 +54,203 @@ impl BlockWrapper {
             BlockWrapper::OneOf(o) => o.finalize(),
             BlockWrapper::Paragraph(p) => p.finalize(),
             BlockWrapper::Answered(_) => vec![],
+            BlockWrapper::Locked(a, _) => a.user_answer(),
+        }
+    }
+    /// Replaces block with its answered version.
+    ///
+    /// `matcher` gets index of blank field.
+    pub fn lock<'a>(
+        &mut self,
+        correct_answer: Vec<Vec<String>>,
+        matcher: impl Fn(usize) -> &'a dyn AnswerMatcher,
+    ) {
+        let (block, user_answer) = match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(o) => (o.as_block(), o.answer()),
+            BlockWrapper::Paragraph(p) => (p.as_block(), p.answer()),
+            BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => return,
+        };
+        *self = BlockWrapper::Locked(
+            answered_block_wrapper::AnsweredBlockWrapper::new(
+                block,
+                user_answer,
+                correct_answer,
+                matcher,
+            ),
+            self.hints_used(),
+        );
+    }
+    /// Block without answers, `None` for answered blocks.
+    pub fn as_block(&self) -> Option<s_text_input_f::Block> {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(o) => Some(o.as_block()),
+            BlockWrapper::Paragraph(p) => Some(p.as_block()),
+            BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => None,
+        }
+    }
+    /// Current answer in the same format as [`finalize`](Self::finalize) returns.
+    pub fn answer(&self) -> Vec<String> {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(o) => o.answer(),
+            BlockWrapper::Paragraph(p) => p.answer(),
+            BlockWrapper::Answered(_) => vec![],
+            BlockWrapper::Locked(a, _) => a.user_answer(),
+        }
+    }
+    /// Number of changes of text in blank fields, `0` for locked blocks.
+    pub fn edits(&self) -> usize {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::Paragraph(p) => p.edits(),
+            BlockWrapper::OneOf(_) | BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => 0,
+        }
+    }
+    /// Number of selection changes in `one of` block, `0` for locked blocks.
+    pub fn selection_changes(&self) -> usize {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(o) => o.selection_changes(),
+            BlockWrapper::Paragraph(_) | BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => 0,
+        }
+    }
+    /// # Returns
+    /// number of hints used for each blank field
+    pub fn hints_used(&self) -> Vec<usize> {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(_) | BlockWrapper::Answered(_) => vec![],
+            BlockWrapper::Paragraph(p) => p.hints_used(),
+            BlockWrapper::Locked(_, hints) => hints.clone(),
+        }
+    }
+    /// Validates each blank field, `get` takes index of blank field.
+    ///
+    /// # Returns
+    /// index of first invalid blank field
+    pub fn validate<'a>(
+        &mut self,
+        get: impl Fn(usize) -> Option<&'a dyn Validator>,
+        labels: &Labels,
+    ) -> Option<usize> {
+        if let BlockWrapper::Paragraph(p) = self {
+            p.validate(get, labels)
+        } else {
+            None
+        }
+    }
+    /// Sets [`BlankOptions`] of each blank field, `get` takes index of blank field.
+    pub fn set_blank_options<'a>(&mut self, get: impl Fn(usize) -> Option<&'a BlankOptions>) {
+        if let BlockWrapper::Paragraph(p) = self {
+            p.set_blank_options(get);
+        }
+    }
+    pub fn prefill(&mut self, answer: Vec<String>) {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(o) => o.prefill(answer),
+            BlockWrapper::Paragraph(p) => p.prefill(answer),
+            BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => (),
         }
     }
+    /// `correct_answer` is used for hints.
+    ///
+    /// `render` also gets cursor position in rendered lines.
     pub fn get_input(
         &mut self,
-        start_from_left: bool,
-        render: &mut impl FnMut(Vec<Line>) -> std::io::Result<()>,
+        entry: Entry,
+        block: usize,
+        options: &Options,
+        correct_answer: Option<&[Vec<String>]>,
+        deadline: Option<Instant>,
+        render: &mut impl FnMut(Vec<Line>, CursorPosition, Popup) -> std::io::Result<()>,
     ) -> Option<std::io::Result<ResultKind>> {
         match self {
             BlockWrapper::Order => todo!(),
             BlockWrapper::AnyOf => todo!(),
-            BlockWrapper::OneOf(o) => o.get_input(start_from_left, render),
-            BlockWrapper::Paragraph(p) => {
-                p.get_input(start_from_left, &mut |line| render(vec![line]))
+            BlockWrapper::OneOf(o) => {
+                o.get_input(entry, options, deadline, &mut |lines, cursor| {
+                    render(lines, cursor, Popup::default())
+                })
             }
-            BlockWrapper::Answered(_) => None,
+            BlockWrapper::Paragraph(p) => p.get_input(
+                entry,
+                block,
+                options,
+                correct_answer,
+                deadline,
+                &mut |line, column, popup| {
+                    render(vec![line], CursorPosition { line: 0, column }, popup)
+                },
+            ),
+            BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => None,
+        }
+    }
+    /// Returns `true` if block can get focus.
+    pub fn has_inputs(&self) -> bool {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(o) => o.has_inputs(),
+            BlockWrapper::Paragraph(p) => p.has_inputs(),
+            BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => false,
+        }
+    }
+    /// Select `n`-th input to be focused with [`Entry::Current`].
+    ///
+    /// # Errors
+    /// if there is no such input
+    pub fn select_placeholder(&mut self, n: usize) -> Option<()> {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(o) => o.select_placeholder(n),
+            BlockWrapper::Paragraph(p) => p.select_placeholder(n),
+            BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => None,
+        }
+    }
+    /// # Returns
+    /// index of first input without answer
+    pub fn first_unanswered(&self) -> Option<usize> {
+        match self {
+            BlockWrapper::Order => todo!(),
+            BlockWrapper::AnyOf => todo!(),
+            BlockWrapper::OneOf(o) => o.first_unanswered(),
+            BlockWrapper::Paragraph(p) => p.first_unanswered(),
+            BlockWrapper::Answered(_) | BlockWrapper::Locked(..) => None,
         }
     }
-    pub fn as_lines(&self) -> Vec<Line> {
+    pub fn as_lines(&self, options: &Options) -> Vec<Line<'_>> {
         match self {
             BlockWrapper::Order => todo!(),
             BlockWrapper::AnyOf => todo!(),
-            BlockWrapper::OneOf(o) => o.as_lines(),
-            BlockWrapper::Paragraph(p) => vec![p.as_line()],
-            BlockWrapper::Answered(a) => a.as_lines(),
+            BlockWrapper::OneOf(o) => o.as_lines(options),
+            BlockWrapper::Paragraph(p) => vec![p.as_line(options)],
+            BlockWrapper::Answered(a) | BlockWrapper::Locked(a, _) => a.as_lines(options),
         }
     }
 }
 
 mod one_of_wrapper;
 pub mod paragraph_wrapper;
-mod answered_block_wrapper {
+pub mod answered_block_wrapper {
     use answered_one_of_wrapper::AnsweredOneOfWrapper;
     use answered_paragraph_wrapper::AnsweredParagraphWrapper;
     use ratatui::text::Line;
 
+    use crate::{answer_matcher::AnswerMatcher, Options};
+
     #[derive(Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum AnsweredBlockWrapper {
         Order,
         AnyOf,
@@ -96,8 +276,8 @@ mod answered_block_wrapper {
                     correct_answer,
                 } => Self::OneOf(AnsweredOneOfWrapper::new(
                     items,
-                    user_answer,
-                    correct_answer,
+                    Some(user_answer),
+                    vec![correct_answer],
                 )),
                 s_text_input_f::BlockAnswered::Paragraph(p) => {
                     Self::Paragraph(AnsweredParagraphWrapper::from(p))
@@ -107,13 +287,62 @@ mod answered_block_wrapper {
         }
     }
     impl AnsweredBlockWrapper {
-        pub fn as_lines(&self) -> Vec<Line> {
+        /// Unlike [`s_text_input_f::to_answered`] accepts unanswered `one of` blocks(empty
+        /// `user_answer`) and several accepted answers for each item of `correct_answer`.
+        ///
+        /// `matcher` gets index of blank field.
+        pub fn new<'a>(
+            block: s_text_input_f::Block,
+            user_answer: Vec<String>,
+            correct_answer: Vec<Vec<String>>,
+            matcher: impl Fn(usize) -> &'a dyn AnswerMatcher,
+        ) -> Self {
+            match block {
+                s_text_input_f::Block::Order(_) => todo!("`order` answer not implemented"),
+                s_text_input_f::Block::AnyOf(_) => todo!("`any_of` answer not implemented"),
+                s_text_input_f::Block::OneOf(items) => {
+                    let user_answer = s_text_input_f::response_as_one_of(user_answer)
+                        .and_then(|x| x.ok())
+                        .filter(|&x| x < items.len());
+                    let [correct_answer]: [Vec<String>; 1] = correct_answer
+                        .try_into()
+                        .expect("`one of` block must have one correct answer");
+                    let correct_answer = correct_answer
+                        .iter()
+                        .map(|x| x.parse().expect("correct answer must be a number"))
+                        .collect();
+                    Self::OneOf(AnsweredOneOfWrapper::new(
+                        items,
+                        user_answer,
+                        correct_answer,
+                    ))
+                }
+                s_text_input_f::Block::Paragraph(p) => Self::Paragraph(
+                    AnsweredParagraphWrapper::new(p, user_answer, correct_answer, matcher),
+                ),
+                s_text_input_f::Block::Answered(_) => {
+                    panic!("You can't answer already answered block")
+                }
+                _ => todo!(),
+            }
+        }
+        /// Answer in the same format as [`BlockWrapper::finalize`](super::BlockWrapper::finalize)
+        /// returns.
+        pub fn user_answer(&self) -> Vec<String> {
             match self {
                 AnsweredBlockWrapper::Order => todo!(),
                 AnsweredBlockWrapper::AnyOf => todo!(),
-                AnsweredBlockWrapper::OneOf(x) => x.as_lines(),
+                AnsweredBlockWrapper::OneOf(x) => x.user_answer(),
+                AnsweredBlockWrapper::Paragraph(x) => x.user_answer(),
+            }
+        }
+        pub fn as_lines(&self, options: &Options) -> Vec<Line<'_>> {
+            match self {
+                AnsweredBlockWrapper::Order => todo!(),
+                AnsweredBlockWrapper::AnyOf => todo!(),
+                AnsweredBlockWrapper::OneOf(x) => x.as_lines(options),
                 AnsweredBlockWrapper::Paragraph(x) => {
-                    vec![x.as_line()]
+                    vec![x.as_line(options)]
                 }
             }
         }
@@ -121,39 +350,81 @@ mod answered_block_wrapper {
 
     mod answered_paragraph_wrapper;
     mod answered_one_of_wrapper {
-        use ratatui::{
-            style::{Style, Stylize},
-            text::{Line, Span},
+        use ratatui::text::{Line, Span};
+
+        use crate::{
+            theme::{CORRECT_MARKER, INCORRECT_MARKER},
+            Options,
         };
 
         #[derive(Debug)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub struct AnsweredOneOfWrapper {
             items: Vec<String>,
-            user_answer: usize,
-            correct_answer: usize,
+            /// `None` if nothing was selected
+            user_answer: Option<usize>,
+            /// Accepted answers
+            correct_answer: Vec<usize>,
         }
         impl AnsweredOneOfWrapper {
-            pub fn new(items: Vec<String>, user_answer: usize, correct_answer: usize) -> Self {
+            pub fn new(
+                items: Vec<String>,
+                user_answer: Option<usize>,
+                correct_answer: Vec<usize>,
+            ) -> Self {
                 Self {
                     items,
                     user_answer,
                     correct_answer,
                 }
             }
-            pub fn as_lines(&self) -> Vec<Line> {
+            pub fn user_answer(&self) -> Vec<String> {
+                self.user_answer.iter().map(|x| x.to_string()).collect()
+            }
+            pub fn as_lines(&self, options: &Options) -> Vec<Line<'_>> {
                 let mut lines = self
                     .items
                     .iter()
-                    .map(|x| Line::from(vec![Span::raw(" -  ").white(), Span::raw(x.as_str())]))
+                    .enumerate()
+                    .map(|(i, x)| {
+                        Line::from(vec![
+                            Span::styled(
+                                options.one_of_prefix.label(i, &options.labels),
+                                options.theme.answered_bullet,
+                            ),
+                            Span::raw(x.as_str()),
+                        ])
+                    })
                     .collect::<Vec<_>>();
-                if self.user_answer != self.correct_answer {
-                    lines[self.user_answer] = lines[self.user_answer]
+                match self.user_answer {
+                    Some(user_answer) if !self.correct_answer.contains(&user_answer) => {
+                        lines[user_answer] = lines[user_answer]
+                            .to_owned()
+                            .patch_style(options.theme.incorrect);
+                    }
+                    Some(_) => (),
+                    None => lines.push(Line::styled(
+                        format!("    {}", options.labels.not_answered),
+                        options.theme.empty_blank,
+                    )),
+                }
+                for &correct_answer in &self.correct_answer {
+                    lines[correct_answer] = lines[correct_answer]
                         .to_owned()
-                        .patch_style(Style::new().red());
+                        .patch_style(options.theme.correct);
+                }
+                if options.theme.markers {
+                    for (i, line) in lines.iter_mut().take(self.items.len()).enumerate() {
+                        let marker = if self.correct_answer.contains(&i) {
+                            CORRECT_MARKER
+                        } else if self.user_answer == Some(i) {
+                            INCORRECT_MARKER
+                        } else {
+                            " "
+                        };
+                        line.spans.insert(0, Span::raw(marker));
+                    }
                 }
-                lines[self.correct_answer] = lines[self.correct_answer]
-                    .to_owned()
-                    .patch_style(Style::new().green());
                 lines
             }
         }
@@ -1,16 +1,21 @@
+use std::time::Instant;
+
 use crossterm::event::{KeyCode, KeyEventKind};
-use ratatui::{
-    style::{Style, Stylize},
-    text::{Line, Span},
-};
+use ratatui::text::{Line, Span};
 
-use crate::ResultKind;
+use super::Entry;
+use crate::{
+    timer::{self, TimedEvent},
+    CursorPosition, Options, ResultKind, Theme,
+};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OneOfWrapper {
     items: Vec<String>,
     cursor: usize,
     selected: Option<usize>,
+    selection_changes: usize,
 }
 impl From<Vec<String>> for OneOfWrapper {
     fn from(items: Vec<String>) -> Self {
@@ -18,41 +23,84 @@ impl From<Vec<String>> for OneOfWrapper {
             items,
             cursor: 0,
             selected: None,
+            selection_changes: 0,
         }
     }
 }
 impl OneOfWrapper {
+    /// # Returns
+    /// empty vec if nothing selected
     pub fn finalize(self) -> Vec<String> {
-        if let Some(selected) = self.selected {
-            vec![selected.to_string()]
-        } else {
-            vec!["0".into()]
+        self.answer()
+    }
+    pub fn answer(&self) -> Vec<String> {
+        self.selected.iter().map(|x| x.to_string()).collect()
+    }
+    /// Number of times user changed selection.
+    pub fn selection_changes(&self) -> usize {
+        self.selection_changes
+    }
+    pub fn as_block(&self) -> s_text_input_f::Block {
+        s_text_input_f::Block::OneOf(self.items.clone())
+    }
+    pub fn prefill(&mut self, answer: Vec<String>) {
+        if let Some(Ok(selected)) = s_text_input_f::response_as_one_of(answer) {
+            if selected < self.items.len() {
+                self.selected = Some(selected);
+                self.cursor = selected;
+            }
         }
     }
     pub fn get_input(
         &mut self,
-        start_from_left: bool,
-        render: &mut impl FnMut(Vec<Line>) -> std::io::Result<()>,
+        entry: Entry,
+        options: &Options,
+        deadline: Option<Instant>,
+        render: &mut impl FnMut(Vec<Line>, CursorPosition) -> std::io::Result<()>,
     ) -> Option<std::io::Result<ResultKind>> {
-        if start_from_left {
-            self.select_first_placeholder()?;
-        } else {
-            self.select_last_placeholder()?;
+        match entry {
+            Entry::First => self.select_first_placeholder()?,
+            Entry::Last => self.select_last_placeholder()?,
+            Entry::Current => self.select_placeholder(self.cursor)?,
         }
         let mut render = |one_of: &OneOfWrapper| {
-            let mut lines = one_of.as_lines();
+            let mut lines = one_of.as_lines(options);
             lines[one_of.cursor] = lines[one_of.cursor]
                 .to_owned()
-                .patch_style(current_line_styles());
-            render(lines)
+                .patch_style(options.theme.focused);
+            let cursor = CursorPosition {
+                line: one_of.cursor,
+                column: 0,
+            };
+            render(lines, cursor)
         };
         Some((|| loop {
             render(self)?;
-            if let Some(event) = self.handle_event(Self::get_event()?) {
+            if let Some(event) = self.handle_event(self.get_event(options, deadline)?, options) {
                 break Ok(event);
             }
         })())
     }
+    pub fn has_inputs(&self) -> bool {
+        !self.items.is_empty()
+    }
+    pub fn first_unanswered(&self) -> Option<usize> {
+        if self.selected.is_none() && self.has_inputs() {
+            Some(0)
+        } else {
+            None
+        }
+    }
+    /// # Errors
+    /// if there is no such item
+    pub fn select_placeholder(&mut self, n: usize) -> Option<()> {
+        if n < self.items.len() {
+            self.cursor = n;
+            Some(())
+        } else {
+            None
+        }
+    }
     /// # Errors
     /// if there is no items
     fn select_first_placeholder(&mut self) -> Option<()> {
@@ -106,12 +154,23 @@ impl OneOfWrapper {
         }
     }
 
-    pub fn as_lines(&self) -> Vec<Line> {
-        let mut lines = self.items.iter().map(|x| as_line(x)).collect::<Vec<_>>();
+    pub fn as_lines(&self, options: &Options) -> Vec<Line<'_>> {
+        let mut lines = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                as_line(
+                    options.one_of_prefix.label(i, &options.labels),
+                    x,
+                    &options.theme,
+                )
+            })
+            .collect::<Vec<_>>();
         if let Some(selected) = self.selected {
             lines[selected] = lines[selected]
                 .to_owned()
-                .patch_style(selected_line_styles());
+                .patch_style(options.theme.selected);
         }
         lines
     }
@@ -119,6 +178,7 @@ impl OneOfWrapper {
 #[derive(Clone, Copy)]
 enum Event {
     Select,
+    Shortcut(usize),
     NextItem,
     PrevItem,
     NextBlock,
@@ -126,16 +186,30 @@ enum Event {
     PrevBlock,
     Redraw,
     Cancel,
+    TimedOut,
 }
 impl OneOfWrapper {
     #[allow(clippy::too_many_lines)]
-    fn get_event() -> std::io::Result<Event> {
+    fn get_event(&self, options: &Options, deadline: Option<Instant>) -> std::io::Result<Event> {
+        let shortcut = |c| {
+            options
+                .one_of_prefix
+                .shortcut(c)
+                .filter(|&i| i < self.items.len())
+        };
         Ok({
             loop {
-                if let Some(x) = match crossterm::event::read()? {
-                    crossterm::event::Event::Key(k) => {
-                        if k.kind == KeyEventKind::Press {
+                if let Some(x) = match timer::read(deadline)? {
+                    TimedEvent::TimedOut => Some(Event::TimedOut),
+                    TimedEvent::Tick | TimedEvent::Event(crossterm::event::Event::Resize(_, _)) => {
+                        Some(Event::Redraw)
+                    }
+                    TimedEvent::Event(crossterm::event::Event::Key(k)) => {
+                        if matches!(k.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
                             match k.code {
+                                KeyCode::Char(c) if shortcut(c).is_some() => {
+                                    shortcut(c).map(Event::Shortcut)
+                                }
                                 KeyCode::Char(' ') => Some(Event::Select),
                                 KeyCode::Enter => Some(Event::EnterKey),
                                 KeyCode::Down | KeyCode::Char('j' | 'J') => Some(Event::NextItem),
@@ -161,20 +235,28 @@ impl OneOfWrapper {
                             None
                         }
                     }
-                    crossterm::event::Event::Resize(_, _) => Some(Event::Redraw),
-                    _ => None,
+                    TimedEvent::Event(_) => None,
                 } {
                     break x;
                 }
             }
         })
     }
-    fn handle_event(&mut self, event: Event) -> Option<ResultKind> {
+    fn handle_event(&mut self, event: Event, options: &Options) -> Option<ResultKind> {
         match event {
             Event::Select => {
-                self.selected = Some(self.cursor);
+                self.select(self.cursor);
                 None
             }
+            Event::Shortcut(i) => {
+                self.cursor = i;
+                self.select(i);
+                if options.submit_on_shortcut {
+                    Some(ResultKind::Ok)
+                } else {
+                    None
+                }
+            }
             Event::NextItem => {
                 let already_last_elem = !self.select_next_placeholder().unwrap();
                 if already_last_elem {
@@ -209,7 +291,7 @@ impl OneOfWrapper {
                     Some(ResultKind::Ok)
                 } else {
                     if cfg!(feature = "fast_select_with_enter") {
-                        self.selected = Some(self.cursor);
+                        self.select(self.cursor);
                         Some(ResultKind::Ok)
                     } else {
                         self.select_next_placeholder().unwrap();
@@ -231,15 +313,16 @@ impl OneOfWrapper {
             }
             Event::Redraw => None,
             Event::Cancel => Some(ResultKind::Canceled),
+            Event::TimedOut => Some(ResultKind::TimedOut),
+        }
+    }
+    fn select(&mut self, i: usize) {
+        if self.selected != Some(i) {
+            self.selected = Some(i);
+            self.selection_changes += 1;
         }
     }
 }
-fn as_line(s: &str) -> Line {
-    Line::from(vec![Span::raw(" -  ").blue(), Span::raw(s)]).italic()
-}
-fn current_line_styles() -> Style {
-    Style::new().bold().fg(ratatui::style::Color::Magenta)
-}
-fn selected_line_styles() -> Style {
-    Style::new().bold().not_italic()
+fn as_line<'a>(prefix: String, s: &'a str, theme: &Theme) -> Line<'a> {
+    Line::from(vec![Span::styled(prefix, theme.bullet), Span::raw(s)]).style(theme.item)
 }
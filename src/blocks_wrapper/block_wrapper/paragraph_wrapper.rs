@@ -1,8 +1,20 @@
 use paragraph_item_wrapper::*;
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 
-use crate::{split_at_mid, ResultKind};
+use std::time::Instant;
+
+use crate::{
+    completion::{Completer, Popup},
+    HintOptions,
+};
+
+use super::Entry;
+use crate::{
+    blank_field::BlankField, split_at_mid, validator::Validator, BlankOptions, Labels, Options,
+    ResultKind,
+};
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParagraphWrapper {
     items: Vec<ParagraphItemWrapper>,
     cursor: usize,
@@ -22,22 +34,122 @@ impl ParagraphWrapper {
             .filter_map(|x| x.finalize().ok())
             .collect()
     }
+    pub fn answer(&self) -> Vec<String> {
+        self.placeholders()
+            .filter_map(|x| x.as_blank_field())
+            .map(|x| x.text())
+            .collect()
+    }
+    pub fn as_block(&self) -> s_text_input_f::Block {
+        s_text_input_f::Block::Paragraph(
+            self.items
+                .iter()
+                .map(|x| match x {
+                    ParagraphItemWrapper::Text(s) => s_text_input_f::ParagraphItem::Text(s.clone()),
+                    ParagraphItemWrapper::Placeholder(_) => {
+                        s_text_input_f::ParagraphItem::Placeholder
+                    }
+                })
+                .collect(),
+        )
+    }
+    pub fn prefill(&mut self, answer: Vec<String>) {
+        self.items
+            .iter_mut()
+            .filter(|x| x.is_placeholder())
+            .zip(answer)
+            .filter_map(|(x, answer)| Some((x.as_placeholder()?, answer)))
+            .for_each(|(x, answer)| {
+                let options = x.options.clone();
+                *x = BlankField::new(&answer);
+                x.set_options(options);
+            });
+    }
+    /// Validates each blank field, `get` takes index of blank field.
+    ///
+    /// # Returns
+    /// index of first invalid blank field
+    pub fn validate<'a>(
+        &mut self,
+        get: impl Fn(usize) -> Option<&'a dyn Validator>,
+        labels: &Labels,
+    ) -> Option<usize> {
+        self.items
+            .iter_mut()
+            .filter_map(|x| x.as_placeholder())
+            .enumerate()
+            .map(|(i, x)| (i, x.validate(get(i), labels)))
+            .filter(|(_, valid)| !valid)
+            .map(|(i, _)| i)
+            .min()
+    }
+    /// Sets [`BlankOptions`] of each blank field, `get` takes index of blank field.
+    pub fn set_blank_options<'a>(&mut self, get: impl Fn(usize) -> Option<&'a BlankOptions>) {
+        self.items
+            .iter_mut()
+            .filter_map(|x| x.as_placeholder())
+            .enumerate()
+            .for_each(|(i, x)| x.set_options(get(i).cloned().unwrap_or_default()));
+    }
+    /// Number of changes of text in all blank fields.
+    pub fn edits(&self) -> usize {
+        self.placeholders()
+            .filter_map(|x| x.as_blank_field())
+            .map(|x| x.edits)
+            .sum()
+    }
+    pub fn hints_used(&self) -> Vec<usize> {
+        self.placeholders()
+            .filter_map(|x| x.as_blank_field())
+            .map(|x| x.hints)
+            .collect()
+    }
+    /// `correct_answer` contains accepted answers for each blank field, used for hints.
+    /// `block` is index of this block, used for [`Options::completers`].
+    ///
+    /// `render` also gets column of cursor and suggestions.
     #[allow(clippy::too_many_lines)]
     pub fn get_input(
         &mut self,
-        start_from_left: bool,
-        render: &mut impl FnMut(Line) -> std::io::Result<()>,
+        entry: Entry,
+        block: usize,
+        options: &Options,
+        correct_answer: Option<&[Vec<String>]>,
+        deadline: Option<Instant>,
+        render: &mut impl FnMut(Line, usize, Popup) -> std::io::Result<()>,
     ) -> Option<std::io::Result<ResultKind>> {
-        if start_from_left {
-            self.select_first_placeholder()?;
-        } else {
-            self.select_last_placeholder()?;
+        match entry {
+            Entry::First => self.select_first_placeholder()?,
+            Entry::Last => self.select_last_placeholder()?,
+            Entry::Current => {
+                if !self.get_current()?.is_placeholder() {
+                    self.select_first_placeholder()?;
+                }
+            }
         }
 
         let result_kind = loop {
             let (head, current_placeholder, tail) =
                 split_at_mid(&mut self.items, self.cursor).unwrap();
-            let get_input_result = get_input(current_placeholder, head, tail, render).unwrap();
+            let blank = head.iter().filter(|x| x.is_placeholder()).count();
+            let hint = options.hint.as_ref().zip(
+                correct_answer
+                    .and_then(|x| x.get(blank))
+                    .and_then(|x| x.first())
+                    .map(String::as_str),
+            );
+            let completer = options.completers.get(block, blank);
+            let get_input_result = get_input(
+                current_placeholder,
+                head,
+                tail,
+                hint,
+                completer,
+                deadline,
+                options,
+                render,
+            )
+            .unwrap();
             if let Ok(result_kind) = get_input_result {
                 match result_kind {
                     ResultKind::Ok => {
@@ -47,6 +159,7 @@ impl ParagraphWrapper {
                         }
                     }
                     ResultKind::Canceled => break ResultKind::Canceled,
+                    ResultKind::TimedOut => break ResultKind::TimedOut,
                     ResultKind::NextBlock => {
                         let next_elem_exist = self.select_next_placeholder().unwrap();
                         if !next_elem_exist {
@@ -66,6 +179,28 @@ impl ParagraphWrapper {
         };
         Some(Ok(result_kind))
     }
+    pub fn has_inputs(&self) -> bool {
+        self.items.iter().any(|x| x.is_placeholder())
+    }
+    pub fn first_unanswered(&self) -> Option<usize> {
+        self.placeholders()
+            .position(|x| x.as_blank_field().is_some_and(|x| x.is_empty()))
+    }
+    /// # Errors
+    /// if there is no `n`-th placeholder
+    pub fn select_placeholder(&mut self, n: usize) -> Option<()> {
+        self.cursor = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.is_placeholder())
+            .nth(n)?
+            .0;
+        Some(())
+    }
+    fn placeholders(&self) -> impl Iterator<Item = &ParagraphItemWrapper> {
+        self.items.iter().filter(|x| x.is_placeholder())
+    }
     /// # Errors
     /// if there is no placeholders
     fn select_first_placeholder(&mut self) -> Option<()> {
@@ -140,33 +275,62 @@ impl ParagraphWrapper {
         self.items.get_mut(self.cursor)
     }
 
-    pub fn as_line(&self) -> Line {
-        self.items.iter().flat_map(|x| x.as_spans()).collect()
+    pub fn as_line(&self, options: &Options) -> Line<'_> {
+        self.items
+            .iter()
+            .flat_map(|x| x.as_spans(options))
+            .collect()
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn get_input(
     current_placeholder: &mut ParagraphItemWrapper,
     head: &mut [ParagraphItemWrapper],
     tail: &mut [ParagraphItemWrapper],
-    render: &mut impl FnMut(Line) -> Result<(), std::io::Error>,
+    hint: Option<(&HintOptions, &str)>,
+    completer: Option<&dyn Completer>,
+    deadline: Option<Instant>,
+    options: &Options,
+    render: &mut impl FnMut(Line, usize, Popup) -> Result<(), std::io::Error>,
 ) -> Option<Result<ResultKind, std::io::Error>> {
-    current_placeholder.get_input(&mut |current_placeholder_spans| {
-        let head_spans = head.iter().flat_map(|x| x.as_spans());
-        let tail_spans = tail.iter().flat_map(|x| x.as_spans());
-        let line: Line = head_spans
-            .chain(current_placeholder_spans)
-            .chain(tail_spans)
-            .collect();
-        render(line)
-    })
+    current_placeholder.get_input(
+        hint,
+        completer,
+        deadline,
+        options,
+        &mut |current_placeholder_spans, cursor, mut popup| {
+            let head_spans = head
+                .iter()
+                .flat_map(|x| x.as_spans(options))
+                .collect::<Vec<_>>();
+            let head_width = head_spans.iter().map(Span::width).sum::<usize>();
+            let cursor = head_width + cursor;
+            popup.column += head_width;
+            let tail_spans = tail.iter().flat_map(|x| x.as_spans(options));
+            let line: Line = head_spans
+                .into_iter()
+                .chain(current_placeholder_spans)
+                .chain(tail_spans)
+                .collect();
+            render(line, cursor, popup)
+        },
+    )
 }
 
 pub mod paragraph_item_wrapper {
-    use crate::{blank_field::BlankField, ResultKind};
-    use ratatui::{style::Stylize, text::Span};
+    use std::time::Instant;
+
+    use crate::{
+        blank_field::BlankField,
+        completion::{Completer, Popup},
+        HintOptions, Options, ResultKind, Theme,
+    };
+    use ratatui::text::Span;
 
     #[derive(Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[allow(clippy::large_enum_variant)]
     pub enum ParagraphItemWrapper {
         Text(String),
         Placeholder(BlankField),
@@ -182,38 +346,65 @@ pub mod paragraph_item_wrapper {
         }
     }
     impl ParagraphItemWrapper {
+        #[allow(clippy::result_large_err)]
         pub fn finalize(self) -> Result<String, Self> {
             self.try_into_placeholder().map(|x| x.text().to_owned())
         }
         pub fn get_input(
             &mut self,
-            render: &mut impl FnMut(Vec<Span>) -> std::io::Result<()>,
+            hint: Option<(&HintOptions, &str)>,
+            completer: Option<&dyn Completer>,
+            deadline: Option<Instant>,
+            options: &Options,
+            render: &mut impl FnMut(Vec<Span>, usize, Popup) -> std::io::Result<()>,
         ) -> Option<std::io::Result<ResultKind>> {
             let a = self.as_placeholder()?;
             Some((|| {
                 Ok(
-                    match a.get_input(&mut |c| render_active_blank_field(c, render))? {
+                    match a.get_input(
+                        hint,
+                        completer,
+                        options.compose.as_ref(),
+                        deadline,
+                        &mut |c| render_active_blank_field(c, options, render),
+                    )? {
                         ResultKind::Ok => ResultKind::Ok,
                         ResultKind::Canceled => ResultKind::Canceled,
                         ResultKind::NextBlock => ResultKind::NextBlock,
                         ResultKind::PrevBlock => ResultKind::PrevBlock,
+                        ResultKind::TimedOut => ResultKind::TimedOut,
                     },
                 )
             })())
         }
-        pub fn as_spans(&self) -> Vec<Span> {
+        pub fn as_spans(&self, options: &Options) -> Vec<Span<'_>> {
+            let theme = &options.theme;
             match self {
                 ParagraphItemWrapper::Text(s) => vec![s.into()],
                 ParagraphItemWrapper::Placeholder(blank_field) => {
-                    if blank_field.is_empty() {
-                        vec![Span::raw("<empty>").dark_gray().italic()]
+                    let blank_options = &blank_field.options;
+                    let mut spans = if !blank_field.is_empty() {
+                        vec![Span::styled(
+                            blank_field.displayed_text(),
+                            theme.filled_blank,
+                        )]
+                    } else if blank_options.placeholder.is_some() || blank_options.width.is_some() {
+                        vec![]
                     } else {
-                        vec![Span::raw(blank_field.text()).underlined().gray().italic()]
-                    }
+                        vec![Span::styled(
+                            options.labels.empty.clone(),
+                            theme.empty_blank,
+                        )]
+                    };
+                    spans.extend(padding_spans(blank_field, 0, theme));
+                    spans.extend(hint_span(blank_field, options));
+                    spans.extend(error_span(blank_field, options));
+                    spans
                 }
             }
         }
 
+        #[allow(clippy::result_large_err)]
         fn try_into_placeholder(self) -> Result<BlankField, Self> {
             if let Self::Placeholder(v) = self {
                 Ok(v)
@@ -222,7 +413,15 @@ pub mod paragraph_item_wrapper {
             }
         }
 
-        fn as_placeholder(&mut self) -> Option<&mut BlankField> {
+        pub fn as_placeholder(&mut self) -> Option<&mut BlankField> {
+            if let Self::Placeholder(v) = self {
+                Some(v)
+            } else {
+                None
+            }
+        }
+
+        pub fn as_blank_field(&self) -> Option<&BlankField> {
             if let Self::Placeholder(v) = self {
                 Some(v)
             } else {
@@ -240,21 +439,87 @@ pub mod paragraph_item_wrapper {
     }
     fn render_active_blank_field(
         blank_field: &BlankField,
-        render: &mut impl FnMut(Vec<Span>) -> std::io::Result<()>,
+        options: &Options,
+        render: &mut impl FnMut(Vec<Span>, usize, Popup) -> std::io::Result<()>,
     ) -> std::io::Result<()> {
-        render(style_active_blank_field(blank_field))
+        let mut spans = style_active_blank_field(blank_field, options);
+        spans.extend(error_span(blank_field, options));
+        let cursor = spans[0].width();
+        let popup = Popup::new(
+            &blank_field.suggestions,
+            blank_field.suggestion,
+            &options.theme,
+        );
+        render(spans, cursor, popup)
+    }
+    /// First span is text before cursor.
+    pub fn style_active_blank_field<'a>(
+        blank_field: &'a BlankField,
+        options: &'a Options,
+    ) -> Vec<Span<'a>> {
+        let (theme, cursor) = (&options.theme, options.cursor);
+        let echo = blank_field.options.echo;
+        let (a, b) = blank_field.text.split_at(blank_field.cursor);
+        let mut spans = vec![Span::styled(
+            echo.apply(&a.iter().collect::<String>()),
+            theme.active_blank,
+        )];
+        if let Some(pending) = &blank_field.composing {
+            let pending = if pending.is_empty() {
+                &options.labels.composing
+            } else {
+                pending
+            };
+            let pending = pending.as_str();
+            spans.push(Span::styled(pending, theme.dimmed));
+        }
+        if cursor.is_drawn() {
+            spans.push(Span::styled("|", theme.cursor));
+        }
+        spans.push(Span::styled(
+            echo.apply(&b.iter().collect::<String>()),
+            theme.active_blank,
+        ));
+        let cursor_width = usize::from(cursor.is_drawn());
+        spans.extend(padding_spans(blank_field, cursor_width, theme));
+        spans.extend(hint_span(blank_field, options));
+        spans
+    }
+    /// Placeholder text if field is empty and `_` up to expected width, `used` is width taken
+    /// by things other than text, e.g. cursor.
+    fn padding_spans<'a>(blank_field: &'a BlankField, used: usize, theme: &Theme) -> Vec<Span<'a>> {
+        let options = &blank_field.options;
+        let mut spans = Vec::new();
+        let text_width = blank_field.displayed_text().chars().count();
+        let mut used = used + text_width;
+        if let Some(placeholder) = options
+            .placeholder
+            .as_ref()
+            .filter(|_| blank_field.is_empty())
+        {
+            used = used.saturating_sub(text_width) + placeholder.chars().count();
+            spans.push(Span::styled(placeholder.as_str(), theme.empty_blank));
+        }
+        let padding = options.width.unwrap_or_default().saturating_sub(used);
+        if padding > 0 {
+            spans.push(Span::styled("_".repeat(padding), theme.empty_blank));
+        }
+        spans
+    }
+    fn error_span(blank_field: &BlankField, options: &Options) -> Option<Span<'static>> {
+        blank_field.error.as_ref().map(|error| {
+            Span::styled(
+                crate::labels::fill(&options.labels.invalid_blank, &[error]),
+                options.theme.warning,
+            )
+        })
     }
-    pub fn style_active_blank_field(blank_field: &BlankField) -> Vec<Span> {
-        let chars = &blank_field.text;
-        let (a, b) = chars.split_at(blank_field.cursor);
-        vec![
-            Span::raw(a.iter().collect::<String>())
-                .underlined()
-                .italic(),
-            Span::raw("|").blue(),
-            Span::raw(b.iter().collect::<String>())
-                .underlined()
-                .italic(),
-        ]
+    fn hint_span(blank_field: &BlankField, options: &Options) -> Option<Span<'static>> {
+        blank_field.hint.as_ref().map(|hint| {
+            Span::styled(
+                crate::labels::fill(&options.labels.hint, &[hint]),
+                options.theme.dimmed,
+            )
+        })
     }
 }
@@ -0,0 +1,305 @@
+use std::io::{BufRead, Write};
+
+use super::BlocksWrapper;
+use crate::{labels::fill, Options, ResultKind};
+
+impl BlocksWrapper {
+    /// Line oriented alternative to [`get_input`](Self::get_input), e.g. for screen readers.
+    ///
+    /// Each block is announced and answered with lines read from `input`: number of item for
+    /// `one of` blocks, text for each blank field. Empty line keeps current answer. After each
+    /// block user confirms the answer or answers it again. Answers are checked with
+    /// [`Options::validators`].
+    ///
+    /// Hints, time limits, immediate feedback and [`Options::blanks`] are not supported.
+    ///
+    /// # Returns
+    /// - `None` if there is no blocks with inputs
+    /// - [`ResultKind::Canceled`] if `input` ends
+    pub fn get_input_linear(
+        &mut self,
+        options: &Options,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> Option<std::io::Result<ResultKind>> {
+        let blocks = (0..self.items.len())
+            .filter(|&i| self.items[i].has_inputs())
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return None;
+        }
+        Some((|| {
+            for (n, &i) in blocks.iter().enumerate() {
+                loop {
+                    let block_number =
+                        fill(&options.labels.block_number, &[&(n + 1), &blocks.len()]);
+                    writeln!(output, "{block_number}")?;
+                    let answer = match self.items[i].as_block() {
+                        Some(s_text_input_f::Block::OneOf(items)) => {
+                            let current = self.items[i].answer();
+                            read_one_of(&items, current, options, input, output)?
+                        }
+                        Some(s_text_input_f::Block::Paragraph(items)) => {
+                            let current = self.items[i].answer();
+                            let validate = |blank, text: &str| {
+                                options
+                                    .validators
+                                    .get(i, blank)
+                                    .map_or(Ok(()), |x| x.validate(text, &options.labels))
+                            };
+                            read_paragraph(&items, current, validate, options, input, output)?
+                        }
+                        _ => unreachable!("block with inputs"),
+                    };
+                    let Some(answer) = answer else {
+                        return Ok(ResultKind::Canceled);
+                    };
+                    self.items[i].prefill(answer);
+                    let Some(confirmed) = read_confirmation(options, input, output)? else {
+                        return Ok(ResultKind::Canceled);
+                    };
+                    if confirmed {
+                        break;
+                    }
+                }
+            }
+            Ok(ResultKind::Ok)
+        })())
+    }
+}
+
+/// # Returns
+/// `None` if `input` ended
+fn read_line(
+    prompt: &str,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> std::io::Result<Option<String>> {
+    write!(output, "{prompt}")?;
+    output.flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        writeln!(output)?;
+        return Ok(None);
+    }
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_owned()))
+}
+
+fn read_one_of(
+    items: &[String],
+    current: Vec<String>,
+    options: &Options,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> std::io::Result<Option<Vec<String>>> {
+    let labels = &options.labels;
+    writeln!(output, "{}", fill(&labels.choose_one_of, &[&items.len()]))?;
+    for (i, item) in items.iter().enumerate() {
+        writeln!(output, "{}", fill(&labels.numbered_item, &[&(i + 1), item]))?;
+    }
+    let current = s_text_input_f::response_as_one_of(current).and_then(Result::ok);
+    if let Some(current) = current {
+        writeln!(
+            output,
+            "{}",
+            fill(&labels.current_answer, &[&(current + 1)])
+        )?;
+    }
+    loop {
+        let Some(line) = read_line(&labels.number_prompt, input, output)? else {
+            return Ok(None);
+        };
+        let line = line.trim();
+        let selected = match line.parse::<usize>() {
+            _ if line.is_empty() && (current.is_some() || !options.require_all_answered) => current,
+            Ok(n @ 1..) if n <= items.len() => Some(n - 1),
+            _ => {
+                writeln!(output, "{}", fill(&labels.invalid_number, &[&items.len()]))?;
+                continue;
+            }
+        };
+        let answer = selected.map_or(&labels.not_answered, |x| &items[x]);
+        writeln!(output, "{}", fill(&labels.your_answer, &[answer]))?;
+        return Ok(Some(selected.iter().map(|x| x.to_string()).collect()));
+    }
+}
+
+fn read_paragraph(
+    items: &[s_text_input_f::ParagraphItem],
+    current: Vec<String>,
+    validate: impl Fn(usize, &str) -> Result<(), String>,
+    options: &Options,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> std::io::Result<Option<Vec<String>>> {
+    let labels = &options.labels;
+    let mut blank = 0;
+    let mut text = String::new();
+    for item in items {
+        match item {
+            s_text_input_f::ParagraphItem::Text(s) => text.push_str(s),
+            s_text_input_f::ParagraphItem::Placeholder => {
+                blank += 1;
+                text.push_str(&fill(&labels.blank, &[&blank]));
+            }
+        }
+    }
+    writeln!(output, "{}", fill(&labels.fill_blanks, &[&text]))?;
+    let mut answer = Vec::new();
+    for (i, current) in current.into_iter().enumerate() {
+        if !current.trim().is_empty() {
+            let current_answer = fill(&labels.current_blank_answer, &[&(i + 1), &current]);
+            writeln!(output, "{current_answer}")?;
+        }
+        let prompt = fill(&labels.blank_prompt, &[&(i + 1)]);
+        let line = loop {
+            let Some(line) = read_line(&prompt, input, output)? else {
+                return Ok(None);
+            };
+            let line = if line.trim().is_empty() {
+                current.clone()
+            } else {
+                line
+            };
+            if line.trim().is_empty() {
+                if options.require_all_answered {
+                    writeln!(output, "{}", labels.answer_required)?;
+                } else {
+                    break line;
+                }
+            } else if let Err(error) = validate(i, &line) {
+                writeln!(output, "{error}")?;
+            } else {
+                break line;
+            }
+        };
+        answer.push(line);
+    }
+    writeln!(
+        output,
+        "{}",
+        fill(&labels.your_answer, &[&answer.join(", ")])
+    )?;
+    Ok(Some(answer))
+}
+
+/// # Returns
+/// `None` if `input` ended
+fn read_confirmation(
+    options: &Options,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> std::io::Result<Option<bool>> {
+    let labels = &options.labels;
+    loop {
+        let Some(line) = read_line(&labels.confirm, input, output)? else {
+            return Ok(None);
+        };
+        let line = line.trim().to_lowercase();
+        if labels.yes.to_lowercase().starts_with(&line) {
+            return Ok(Some(true));
+        } else if labels.no.to_lowercase().starts_with(&line) {
+            return Ok(Some(false));
+        }
+        writeln!(output, "{}", labels.invalid_confirmation)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use s_text_input_f::{Block, ParagraphItem};
+
+    use super::*;
+
+    fn paragraph(blanks: usize) -> Block {
+        let mut items = vec![ParagraphItem::Text("The".to_owned())];
+        for _ in 0..blanks {
+            items.push(ParagraphItem::Text(" ".to_owned()));
+            items.push(ParagraphItem::Placeholder);
+        }
+        Block::Paragraph(items)
+    }
+    fn one_of() -> Block {
+        Block::OneOf(vec!["a".to_owned(), "b".to_owned(), "c".to_owned()])
+    }
+    /// Runs linear input with `input` lines, returns result, answers and output.
+    fn run(
+        mut blocks_wrapper: BlocksWrapper,
+        options: &Options,
+        input: &str,
+    ) -> (ResultKind, Vec<Vec<String>>, String) {
+        let mut output = Vec::new();
+        let result_kind = blocks_wrapper
+            .get_input_linear(options, &mut input.as_bytes(), &mut output)
+            .unwrap()
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        (result_kind, blocks_wrapper.finalize(), output)
+    }
+
+    #[test]
+    fn rejected_answer_is_asked_again() {
+        let blocks = BlocksWrapper::from(vec![one_of()]);
+        let (result_kind, answers, output) = run(blocks, &Options::default(), "2\nn\n3\nY\n");
+        assert_eq!(result_kind, ResultKind::Ok);
+        assert_eq!(answers, [["2"]]);
+        assert!(output.contains("Your answer: b."), "{output}");
+        assert!(output.contains("Current answer: 2."), "{output}");
+        assert!(output.contains("Your answer: c."), "{output}");
+    }
+
+    #[test]
+    fn invalid_input_is_asked_again() {
+        let blocks = BlocksWrapper::from(vec![one_of()]);
+        let (_, answers, output) = run(blocks, &Options::default(), "4\n1\nmaybe\nyes\n");
+        assert_eq!(answers, [["0"]]);
+        assert!(output.contains("Enter number from 1 to 3."), "{output}");
+        assert!(output.contains("Enter y or n."), "{output}");
+    }
+
+    #[test]
+    fn empty_line_keeps_current_answer() {
+        let answers = vec![
+            vec!["cat".to_owned(), "mat".to_owned()],
+            vec!["1".to_owned()],
+        ];
+        let blocks = BlocksWrapper::with_answers(vec![paragraph(2), one_of()], answers);
+        let (result_kind, answers, output) = run(blocks, &Options::default(), "\ndog\n\n\n\n");
+        assert_eq!(result_kind, ResultKind::Ok);
+        assert_eq!(answers, [vec!["cat", "dog"], vec!["1"]]);
+        assert!(output.contains("Block 2 of 2."), "{output}");
+        assert!(
+            output.contains("Current answer for blank 2: mat."),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn require_all_answered() {
+        let options = Options {
+            require_all_answered: true,
+            ..Default::default()
+        };
+        let blocks = BlocksWrapper::from(vec![paragraph(1), one_of()]);
+        let (_, answers, output) = run(blocks, &options, "\nx\n\n\n3\n\n");
+        assert_eq!(answers, [["x"], ["2"]]);
+        assert!(output.contains("Answer is required."), "{output}");
+        assert!(output.contains("Enter number from 1 to 3."), "{output}");
+
+        let blocks = BlocksWrapper::from(vec![paragraph(1), one_of()]);
+        let (_, answers, _) = run(blocks, &Options::default(), "\n\n\n\n");
+        assert_eq!(answers, [vec![""], vec![]]);
+    }
+
+    #[test]
+    fn input_ending_early_cancels() {
+        let blocks = BlocksWrapper::from(vec![paragraph(2)]);
+        let (result_kind, _, _) = run(blocks, &Options::default(), "cat\n");
+        assert_eq!(result_kind, ResultKind::Canceled);
+
+        let blocks = BlocksWrapper::from(vec![one_of(), one_of()]);
+        let (result_kind, answers, _) = run(blocks, &Options::default(), "1\ny\n2\n");
+        assert_eq!(result_kind, ResultKind::Canceled);
+        assert_eq!(answers, [["0"], ["1"]]);
+    }
+}
@@ -1,89 +1,397 @@
-use block_wrapper::BlockWrapper;
+use std::time::{Duration, Instant};
+
+use block_wrapper::{answered_block_wrapper::AnsweredBlockWrapper, BlockWrapper, Entry};
 use ratatui::text::Text;
 
-use crate::{split_at_mid, ResultKind};
+use crate::{
+    answer_matcher::Matchers, explanation, split_at_mid, timer, AcceptedAnswers, BlockMetrics,
+    CursorPosition, HintsUsed, Options, ResultKind, StartPosition,
+};
 
+/// State of interactive input: entered text, selections and focus.
+///
+/// Prefilled answers, correct answers and collected metrics can be combined:
+/// ```no_run
+/// # use ratatui_inputs::{BlocksWrapper, Options};
+/// # fn f(blocks: s_text_input_f::Blocks, saved: s_text_input_f::Response,
+/// #     correct_answer: ratatui_inputs::AcceptedAnswers) -> std::io::Result<()> {
+/// let mut input = BlocksWrapper::with_answers(blocks, saved).with_correct_answer(correct_answer);
+/// if let Some(result_kind) = input.get_input(&Options::default(), &mut |_text| Ok(())) {
+///     result_kind?;
+///     let metrics = input.metrics().to_vec();
+///     let hints_used = input.hints_used();
+///     let answers = input.finalize();
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// With `serde` feature it can be serialized between [`get_input`](Self::get_input) calls and
+/// restored later; use [`StartPosition::Resume`] to continue from the same input.
+///
+/// Correct answers set with [`with_correct_answer`](Self::with_correct_answer) are not
+/// serialized, so saved state doesn't reveal them; set them again after restoring. Blocks already
+/// checked with [`Options::immediate_feedback`] keep their correct answers, as user has seen them.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlocksWrapper {
     items: Vec<BlockWrapper>,
     cursor: usize,
-    start_from_left: bool,
+    entry: Entry,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    correct_answer: Option<AcceptedAnswers>,
+    metrics: Vec<BlockMetrics>,
+    /// Answer of each block when user first left it answered.
+    first_answers: Vec<Option<Vec<String>>>,
+    /// Rendered under corresponding answered blocks.
+    explanations: Vec<Option<String>>,
 }
 impl From<s_text_input_f::Blocks> for BlocksWrapper {
     fn from(value: s_text_input_f::Blocks) -> Self {
+        Self::new(value.into_iter().map(|x| x.into()).collect())
+    }
+}
+impl BlocksWrapper {
+    fn new(items: Vec<BlockWrapper>) -> Self {
         Self {
-            items: value.into_iter().map(|x| x.into()).collect(),
+            metrics: vec![BlockMetrics::default(); items.len()],
+            first_answers: vec![None; items.len()],
+            explanations: Vec::new(),
+            items,
             cursor: 0,
-            start_from_left: true,
+            entry: Entry::First,
+            correct_answer: None,
         }
     }
-}
-impl BlocksWrapper {
+    /// Creates input prefilled with `answers` in the same format as [`finalize`](Self::finalize) returns.
+    ///
+    /// `answers` may be partial: missing and malformed answers are left empty.
+    pub fn with_answers(blocks: s_text_input_f::Blocks, answers: s_text_input_f::Response) -> Self {
+        let mut blocks_wrapper = Self::from(blocks);
+        blocks_wrapper
+            .items
+            .iter_mut()
+            .zip(answers)
+            .for_each(|(block, answer)| block.prefill(answer));
+        blocks_wrapper
+    }
+    /// Blocks with user and correct answers and `explanations` under them, followed by `summary`.
+    pub(crate) fn answered(
+        blocks: s_text_input_f::Blocks,
+        user_answer: s_text_input_f::Response,
+        correct_answer: AcceptedAnswers,
+        explanations: Vec<Option<String>>,
+        matchers: &Matchers,
+        summary: Option<String>,
+    ) -> Self {
+        assert_eq!(user_answer.len(), correct_answer.len());
+        let mut items = blocks
+            .into_iter()
+            .zip(user_answer.into_iter().zip(correct_answer))
+            .enumerate()
+            .map(|(i, (block, (user_answer, correct_answer)))| {
+                BlockWrapper::Answered(AnsweredBlockWrapper::new(
+                    block,
+                    user_answer,
+                    correct_answer,
+                    |blank| matchers.get(i, blank),
+                ))
+            })
+            .collect::<Vec<_>>();
+        if let Some(summary) = summary {
+            items.push(
+                s_text_input_f::Block::Paragraph(vec![s_text_input_f::ParagraphItem::Text(
+                    summary,
+                )])
+                .into(),
+            );
+        }
+        Self {
+            explanations,
+            ..Self::new(items)
+        }
+    }
+    /// Correct answers used for [`Options::immediate_feedback`] and [`Options::hint`].
+    pub fn with_correct_answer(mut self, correct_answer: AcceptedAnswers) -> Self {
+        self.correct_answer = Some(correct_answer);
+        self
+    }
+    pub fn as_text(&self, options: &Options) -> Text<'_> {
+        self.as_text_explained(options, true)
+    }
+    /// Same as [`as_text`](Self::as_text), but explanations can be hidden.
+    pub(crate) fn as_text_explained(&self, options: &Options, show_explanations: bool) -> Text<'_> {
+        self.items
+            .iter()
+            .enumerate()
+            .flat_map(|(i, x)| {
+                let mut lines = x.as_lines(options);
+                if let Some(explanation) = self.explanations.get(i).and_then(Option::as_ref) {
+                    if show_explanations {
+                        lines.extend(explanation::as_lines(explanation, &options.theme));
+                    } else {
+                        lines.push(explanation::hidden_line(options));
+                    }
+                }
+                lines
+            })
+            .collect()
+    }
+    /// Returns `true` if there is explanation for any block.
+    pub(crate) fn has_explanations(&self) -> bool {
+        self.explanations.iter().any(Option::is_some)
+    }
+    /// # Returns
+    /// number of hints used for each blank field of each block
+    pub fn hints_used(&self) -> HintsUsed {
+        self.items.iter().map(|x| x.hints_used()).collect()
+    }
+    /// Interaction metrics for each block, collected by [`get_input`](Self::get_input).
+    pub fn metrics(&self) -> &[BlockMetrics] {
+        &self.metrics
+    }
+    /// Unanswered `one of` blocks give empty vec.
     pub fn finalize(self) -> Vec<Vec<String>> {
         self.items.into_iter().map(|x| x.finalize()).collect()
     }
     pub fn get_input(
         &mut self,
+        options: &Options,
         render: &mut impl FnMut(Text) -> std::io::Result<()>,
     ) -> Option<std::io::Result<ResultKind>> {
-        // TODO: add support for start from first and from last
-        self.select_first_block()?;
+        self.get_input_with_cursor(options, &mut |text, _| render(text))
+    }
+    /// Same as [`get_input`](Self::get_input), but `render` also gets position of cursor in
+    /// rendered text, see [`Options::cursor`].
+    pub fn get_input_with_cursor(
+        &mut self,
+        options: &Options,
+        render: &mut impl FnMut(Text, CursorPosition) -> std::io::Result<()>,
+    ) -> Option<std::io::Result<ResultKind>> {
+        self.set_blank_options(options);
+        self.select_start(options.start_position)?;
+        let deadline = options.deadline_from(Instant::now());
 
         let result_kind = loop {
             let (head, current_block, tail) = split_at_mid(&mut self.items, self.cursor).unwrap();
-            let get_input_result = {
-                let maybe_result = current_block.get_input(
-                    self.start_from_left,
-                    &mut |current_placeholder_lines| {
-                        let head_lines = head.iter().flat_map(|x| x.as_lines());
-                        let tail_lines = tail.iter().flat_map(|x| x.as_lines());
-                        let text: Text = head_lines
-                            .chain(current_placeholder_lines)
-                            .chain(tail_lines)
-                            .collect();
-                        render(text)
-                    },
-                );
-                if let Some(result) = maybe_result {
-                    result
-                } else if self.select_next_block()? {
-                    continue;
-                } else {
-                    return None;
-                }
-            };
-            if let Ok(result_kind) = get_input_result {
-                match result_kind {
-                    ResultKind::Ok => {
-                        let next_elem_exist = self.select_next_block().unwrap();
-                        if !next_elem_exist {
-                            break ResultKind::Ok;
-                        }
-                    }
-                    ResultKind::Canceled => break ResultKind::Canceled,
-                    ResultKind::NextBlock => {
-                        self.select_next_block().unwrap();
+            let correct_answer = self
+                .correct_answer
+                .as_ref()
+                .and_then(|x| x.get(self.cursor))
+                .map(Vec::as_slice);
+            let focused_at = Instant::now();
+            let get_input_result = current_block.get_input(
+                self.entry,
+                self.cursor,
+                options,
+                correct_answer,
+                deadline,
+                &mut |current_placeholder_lines, mut cursor, popup| {
+                    let head_lines = head
+                        .iter()
+                        .flat_map(|x| x.as_lines(options))
+                        .collect::<Vec<_>>();
+                    cursor.line += head_lines.len();
+                    let tail_lines = tail.iter().flat_map(|x| x.as_lines(options));
+                    let mut text: Text = head_lines
+                        .into_iter()
+                        .chain(current_placeholder_lines)
+                        .chain(tail_lines)
+                        .collect();
+                    popup.overlay(&mut text, cursor.line);
+                    if let Some(deadline) = deadline {
+                        text.push_line(timer::countdown_line(deadline, options));
                     }
-                    ResultKind::PrevBlock => {
-                        self.select_prev_block().unwrap();
+                    render(text, cursor)
+                },
+            )?;
+            self.update_metrics(focused_at.elapsed());
+            match get_input_result {
+                Ok(result_kind) => {
+                    if let Some(result_kind) = self.leave_block(result_kind, options) {
+                        break result_kind;
                     }
-                };
-            } else {
-                return Some(get_input_result);
+                }
+                Err(err) => return Some(Err(err)),
             }
         };
         Some(Ok(result_kind))
     }
-    /// # Errors
-    /// if there is no blocks
-    fn select_first_block(&mut self) -> Option<()> {
-        if self.items.is_empty() {
-            None
+    /// Moves focus after user left focused block with `result_kind`.
+    ///
+    /// # Returns
+    /// result of whole input if it's finished
+    fn leave_block(&mut self, result_kind: ResultKind, options: &Options) -> Option<ResultKind> {
+        if matches!(result_kind, ResultKind::Ok | ResultKind::NextBlock) {
+            if let Some(placeholder) = self.validate_block(self.cursor, options) {
+                let block = self.cursor;
+                self.select_start(StartPosition::At { block, placeholder })
+                    .unwrap();
+                return None;
+            }
+        }
+        // only answered blocks are checked, so user can skip blocks and return to them later
+        if matches!(result_kind, ResultKind::Ok | ResultKind::NextBlock)
+            && options.immediate_feedback
+            && self.items[self.cursor].first_unanswered().is_none()
+            && self.lock_current_block(&options.matchers)
+        {
+            if !self.items.iter().any(|x| x.has_inputs()) {
+                return self.can_finish(options).then_some(ResultKind::Ok);
+            }
+            if !self.select_next_block().unwrap() {
+                self.select_prev_block().unwrap();
+            }
+            return None;
+        }
+        match result_kind {
+            ResultKind::Ok => {
+                let next_elem_exist = self.select_next_block().unwrap();
+                if !next_elem_exist && self.can_finish(options) {
+                    return Some(ResultKind::Ok);
+                }
+            }
+            ResultKind::Canceled => return Some(ResultKind::Canceled),
+            ResultKind::TimedOut => return Some(ResultKind::TimedOut),
+            ResultKind::NextBlock => {
+                self.select_next_block().unwrap();
+            }
+            ResultKind::PrevBlock => {
+                self.select_prev_block().unwrap();
+            }
+        };
+        None
+    }
+    /// Focuses first invalid input or, with [`Options::require_all_answered`], first unanswered
+    /// one.
+    ///
+    /// # Returns
+    /// `true` if there is no such input and input can finish
+    fn can_finish(&mut self, options: &Options) -> bool {
+        let first_invalid = (0..self.items.len())
+            .find_map(|block| Some((block, self.validate_block(block, options)?)));
+        if let Some((block, placeholder)) = first_invalid {
+            self.select_start(StartPosition::At { block, placeholder })
+                .unwrap();
+            false
+        } else if options.require_all_answered && !self.is_answered() {
+            self.select_start(StartPosition::FirstUnanswered).unwrap();
+            false
         } else {
-            self.cursor = 0;
-            Some(())
+            true
+        }
+    }
+    fn set_blank_options(&mut self, options: &Options) {
+        for (block, item) in self.items.iter_mut().enumerate() {
+            item.set_blank_options(|blank| options.blanks.get(&(block, blank)));
         }
     }
+    /// Validates blank fields of `block` with [`Options::validators`].
+    ///
+    /// # Returns
+    /// index of first invalid blank field
+    fn validate_block(&mut self, block: usize, options: &Options) -> Option<usize> {
+        self.items[block].validate(
+            |blank| options.validators.get(block, blank),
+            &options.labels,
+        )
+    }
+    /// Updates metrics of focused block after user left it.
+    fn update_metrics(&mut self, time_focused: Duration) {
+        let block = &self.items[self.cursor];
+        let metrics = &mut self.metrics[self.cursor];
+        metrics.time_focused += time_focused;
+        metrics.edits = block.edits();
+        metrics.selection_changes = block.selection_changes();
+        metrics.hints = block.hints_used().iter().sum();
+        let answer = block.answer();
+        match &self.first_answers[self.cursor] {
+            Some(first_answer) => {
+                metrics.changed_after_first_entry |= *first_answer != answer;
+            }
+            None if answer.iter().any(|x| !x.trim().is_empty()) => {
+                self.first_answers[self.cursor] = Some(answer);
+            }
+            None => (),
+        }
+    }
+    /// Replaces focused block with its answered version.
+    ///
+    /// # Returns
+    /// `false` if there is no correct answer for it
+    fn lock_current_block(&mut self, matchers: &Matchers) -> bool {
+        let Some(correct_answer) = self
+            .correct_answer
+            .as_ref()
+            .and_then(|x| x.get(self.cursor))
+        else {
+            return false;
+        };
+        let cursor = self.cursor;
+        self.items[cursor].lock(correct_answer.clone(), |blank| matchers.get(cursor, blank));
+        true
+    }
+    /// Returns `true` if there is no empty blank fields and `one of` blocks without selection.
+    pub fn is_answered(&self) -> bool {
+        self.items.iter().all(|x| x.first_unanswered().is_none())
+    }
+    /// # Errors
+    /// if there is no blocks with inputs
+    fn select_start(&mut self, start_position: StartPosition) -> Option<()> {
+        match start_position {
+            StartPosition::First => self.select_first_block(),
+            StartPosition::Resume => {
+                if self.items.get(self.cursor).is_some_and(|x| x.has_inputs()) {
+                    self.entry = Entry::Current;
+                    Some(())
+                } else {
+                    self.select_first_block()
+                }
+            }
+            StartPosition::Last => self.select_last_block(),
+            StartPosition::At { block, placeholder } => {
+                let selected = self
+                    .items
+                    .get_mut(block)
+                    .and_then(|x| x.select_placeholder(placeholder));
+                if selected.is_some() {
+                    self.cursor = block;
+                    self.entry = Entry::Current;
+                    Some(())
+                } else {
+                    self.select_first_block()
+                }
+            }
+            StartPosition::FirstUnanswered => {
+                let first_unanswered = self
+                    .items
+                    .iter()
+                    .enumerate()
+                    .find_map(|(i, x)| Some((i, x.first_unanswered()?)));
+                if let Some((block, placeholder)) = first_unanswered {
+                    self.select_start(StartPosition::At { block, placeholder })
+                } else {
+                    self.select_first_block()
+                }
+            }
+        }
+    }
+    /// # Errors
+    /// if there is no blocks with inputs
+    fn select_first_block(&mut self) -> Option<()> {
+        self.cursor = self.items.iter().position(|x| x.has_inputs())?;
+        self.entry = Entry::First;
+        Some(())
+    }
+    /// # Errors
+    /// if there is no blocks with inputs
+    fn select_last_block(&mut self) -> Option<()> {
+        self.cursor = self.items.iter().rposition(|x| x.has_inputs())?;
+        self.entry = Entry::Last;
+        Some(())
+    }
+    /// Blocks without inputs are skipped.
+    ///
     /// # Returns
     /// - `Some(true)`  if next block selected
     /// - `Some(false)` if it's last block already
@@ -91,18 +399,20 @@ impl BlocksWrapper {
     fn select_next_block(&mut self) -> Option<bool> {
         if self.items.is_empty() {
             None
+        } else if let Some(x) = self.items[self.cursor + 1..]
+            .iter()
+            .position(|x| x.has_inputs())
+        {
+            self.cursor += 1 + x;
+            self.entry = Entry::First;
+            Some(true)
         } else {
-            self.cursor += 1;
-            if self.cursor < self.items.len() {
-                self.start_from_left = true;
-                Some(true)
-            } else {
-                self.cursor -= 1;
-                self.start_from_left = false;
-                Some(false)
-            }
+            self.entry = Entry::Last;
+            Some(false)
         }
     }
+    /// Blocks without inputs are skipped.
+    ///
     /// # Returns
     /// - `Some(true)`  if prev block selected
     /// - `Some(false)` if it's first block already
@@ -110,15 +420,131 @@ impl BlocksWrapper {
     fn select_prev_block(&mut self) -> Option<bool> {
         if self.items.is_empty() {
             None
-        } else if let Some(x) = self.cursor.checked_sub(1) {
-            self.start_from_left = false;
+        } else if let Some(x) = self.items[..self.cursor]
+            .iter()
+            .rposition(|x| x.has_inputs())
+        {
             self.cursor = x;
+            self.entry = Entry::Last;
             Some(true)
         } else {
-            self.start_from_left = true;
+            self.entry = Entry::First;
             Some(false)
         }
     }
 }
 
 pub mod block_wrapper;
+mod linear;
+
+#[cfg(test)]
+mod tests {
+    use s_text_input_f::{Block, ParagraphItem};
+
+    use super::*;
+
+    fn blocks() -> s_text_input_f::Blocks {
+        vec![
+            Block::OneOf(vec!["a".to_owned(), "b".to_owned()]),
+            Block::Paragraph(vec![
+                ParagraphItem::Text("x ".to_owned()),
+                ParagraphItem::Placeholder,
+                ParagraphItem::Text(" y ".to_owned()),
+                ParagraphItem::Placeholder,
+            ]),
+        ]
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn resume_after_round_trip() {
+        let answers = vec![
+            vec!["1".to_owned()],
+            vec!["first".to_owned(), "second".to_owned()],
+        ];
+        let mut blocks_wrapper = BlocksWrapper::with_answers(blocks(), answers.clone())
+            .with_correct_answer(vec![
+                vec![vec!["1".to_owned()]],
+                vec![vec!["secret".to_owned()]],
+            ]);
+        let at = StartPosition::At {
+            block: 1,
+            placeholder: 1,
+        };
+        blocks_wrapper.select_start(at).unwrap();
+
+        let json = serde_json::to_string(&blocks_wrapper).unwrap();
+        assert!(
+            !json.contains("secret"),
+            "correct answer is serialized: {json}"
+        );
+        let mut restored: BlocksWrapper = serde_json::from_str(&json).unwrap();
+        assert!(restored.correct_answer.is_none());
+        restored.select_start(StartPosition::Resume).unwrap();
+        assert_eq!(restored.cursor, 1);
+        assert_eq!(restored.entry, Entry::Current);
+        assert_eq!(
+            serde_json::to_string(&restored.items).unwrap(),
+            serde_json::to_string(&blocks_wrapper.items).unwrap()
+        );
+        assert_eq!(restored.finalize(), answers);
+    }
+
+    #[test]
+    fn immediate_feedback_requires_all_answered() {
+        let options = Options {
+            immediate_feedback: true,
+            require_all_answered: true,
+            ..Default::default()
+        };
+        let correct_answer = vec![vec![vec!["1".to_owned()]], vec![vec!["a".to_owned()]; 2]];
+        let mut blocks_wrapper = BlocksWrapper::from(blocks()).with_correct_answer(correct_answer);
+        blocks_wrapper.select_start(StartPosition::First).unwrap();
+        // Down past last item of unanswered `one of` block
+        assert_eq!(
+            blocks_wrapper.leave_block(ResultKind::NextBlock, &options),
+            None
+        );
+        assert!(blocks_wrapper.items[0].has_inputs());
+        assert_eq!(blocks_wrapper.cursor, 1);
+        assert_eq!(blocks_wrapper.leave_block(ResultKind::Ok, &options), None);
+        assert!(blocks_wrapper.items[1].has_inputs());
+        assert_eq!(blocks_wrapper.cursor, 0);
+
+        blocks_wrapper.items[0].prefill(vec!["1".to_owned()]);
+        assert_eq!(blocks_wrapper.leave_block(ResultKind::Ok, &options), None);
+        assert!(!blocks_wrapper.items[0].has_inputs());
+        assert_eq!(blocks_wrapper.cursor, 1);
+        blocks_wrapper.items[1].prefill(vec!["a".to_owned(), String::new()]);
+        assert_eq!(
+            blocks_wrapper.leave_block(ResultKind::NextBlock, &options),
+            None
+        );
+        assert!(blocks_wrapper.items[1].has_inputs());
+        blocks_wrapper.items[1].prefill(vec!["a".to_owned(), "b".to_owned()]);
+        assert_eq!(
+            blocks_wrapper.leave_block(ResultKind::Ok, &options),
+            Some(ResultKind::Ok)
+        );
+    }
+
+    #[test]
+    fn immediate_feedback_skips_unanswered_blocks() {
+        let options = Options {
+            immediate_feedback: true,
+            ..Default::default()
+        };
+        let correct_answer = vec![vec![vec!["1".to_owned()]], vec![vec!["a".to_owned()]; 2]];
+        let mut blocks_wrapper = BlocksWrapper::from(blocks()).with_correct_answer(correct_answer);
+        blocks_wrapper.select_start(StartPosition::First).unwrap();
+        assert_eq!(
+            blocks_wrapper.leave_block(ResultKind::NextBlock, &options),
+            None
+        );
+        assert_eq!(
+            blocks_wrapper.leave_block(ResultKind::Ok, &options),
+            Some(ResultKind::Ok)
+        );
+        assert!(blocks_wrapper.items.iter().all(BlockWrapper::has_inputs));
+    }
+}
@@ -0,0 +1,160 @@
+use std::rc::Rc;
+
+use ratatui::text::{Line, Span, Text};
+
+use crate::{per_blank::PerBlank, Theme};
+
+/// Suggests answers for focused blank field, see [`Options::completers`](crate::Options::completers).
+///
+/// Implemented for closures taking text typed so far.
+pub trait Completer {
+    /// Suggestions for `text`, best first.
+    fn complete(&self, text: &str) -> Vec<String>;
+}
+impl<F: Fn(&str) -> Vec<String>> Completer for F {
+    fn complete(&self, text: &str) -> Vec<String> {
+        self(text)
+    }
+}
+
+/// Words starting with typed text and, if `fuzzy`, then words containing its chars in the same
+/// order, e.g. `hse` for `house`. Case is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    pub words: Vec<String>,
+    pub fuzzy: bool,
+}
+impl Completer for WordList {
+    fn complete(&self, text: &str) -> Vec<String> {
+        let text = text.trim().to_lowercase();
+        let (prefixed, other): (Vec<_>, Vec<_>) = self
+            .words
+            .iter()
+            .map(|x| (x, x.to_lowercase()))
+            .partition(|(_, word)| word.starts_with(&text));
+        let fuzzy = other
+            .into_iter()
+            .filter(|(_, word)| self.fuzzy && is_subsequence(&text, word));
+        prefixed
+            .into_iter()
+            .chain(fuzzy)
+            .map(|(x, _)| x.clone())
+            .collect()
+    }
+}
+
+/// Returns `true` if chars of `a` are in `b` in the same order.
+fn is_subsequence(a: &str, b: &str) -> bool {
+    let mut b = b.chars();
+    a.chars().all(|a| b.any(|b| a == b))
+}
+
+/// Default [`Completer`] and completers replacing it for some blank fields.
+#[derive(Debug, Clone, Default)]
+pub struct Completers(PerBlank<dyn Completer>);
+impl Completers {
+    pub fn new(default: impl Completer + 'static) -> Self {
+        Self(PerBlank::new(Rc::new(default)))
+    }
+    /// Suggest answers for `blank`-th blank field of `block`-th block with `completer`.
+    pub fn with_blank(
+        self,
+        block: usize,
+        blank: usize,
+        completer: impl Completer + 'static,
+    ) -> Self {
+        Self(self.0.with_blank(block, blank, Rc::new(completer)))
+    }
+    pub fn get(&self, block: usize, blank: usize) -> Option<&dyn Completer> {
+        self.0.get(block, blank)
+    }
+}
+
+/// Suggestions rendered under focused blank field over following lines.
+#[derive(Debug, Default)]
+pub(crate) struct Popup {
+    /// Column of start of blank field.
+    pub column: usize,
+    pub lines: Vec<Line<'static>>,
+}
+impl Popup {
+    pub(crate) fn new(suggestions: &[String], focused: Option<usize>, theme: &Theme) -> Self {
+        let width = suggestions
+            .iter()
+            .map(|x| Span::raw(x).width())
+            .max()
+            .unwrap_or_default();
+        let lines = suggestions
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let padding = " ".repeat(width - Span::raw(x).width());
+                let (marker, style) = if focused == Some(i) {
+                    (">", theme.focused_suggestion)
+                } else {
+                    (" ", theme.suggestion)
+                };
+                let marker = if theme.markers { marker } else { " " };
+                Line::from(Span::styled(format!("{marker}{x}{padding} "), style))
+            })
+            .collect();
+        Self { column: 0, lines }
+    }
+    /// Draws popup over lines of `text` after `line`, adding lines if needed.
+    pub(crate) fn overlay(self, text: &mut Text, line: usize) {
+        for (i, popup_line) in self.lines.into_iter().enumerate() {
+            let index = line + 1 + i;
+            while text.lines.len() <= index {
+                text.lines.push(Line::default());
+            }
+            let below = std::mem::take(&mut text.lines[index]);
+            let width = popup_line.width();
+            let mut spans = cut(&below, 0, self.column);
+            let left_width = spans.iter().map(Span::width).sum::<usize>();
+            spans.push(Span::raw(" ".repeat(self.column - left_width)));
+            spans.extend(popup_line.spans);
+            spans.extend(cut(&below, self.column + width, usize::MAX));
+            text.lines[index] = Line::from(spans).style(below.style);
+        }
+    }
+}
+
+/// Spans of `line` between columns `start` and `end`, wide chars crossing them are dropped.
+fn cut(line: &Line<'_>, start: usize, end: usize) -> Vec<Span<'static>> {
+    let mut column = 0;
+    let mut spans = Vec::new();
+    for span in &line.spans {
+        let mut content = String::new();
+        for c in span.content.chars() {
+            let width = Span::raw(c.to_string()).width();
+            if column >= start && column + width <= end {
+                content.push(c);
+            }
+            column += width;
+        }
+        if !content.is_empty() {
+            spans.push(Span::styled(content, span.style));
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn popup_drawn_over_following_lines() {
+        let words = ["cat".to_owned(), "car".to_owned()];
+        let mut popup = Popup::new(&words, Some(1), &Theme::accessible());
+        popup.column = 2;
+        let mut text = Text::from(vec![Line::raw("a __"), Line::raw("bbbbbbbb")]);
+        popup.overlay(&mut text, 0);
+        let lines = text
+            .lines
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["a __", "bb cat b", "  >car "]);
+    }
+}
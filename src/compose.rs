@@ -0,0 +1,83 @@
+use crate::ComposeOptions;
+
+/// Combining marks typed after compose key, e.g. `'` and `e` for `é`.
+#[cfg(feature = "unicode-normalization")]
+const MARKS: &[(char, char)] = &[
+    ('\'', '\u{301}'),
+    ('`', '\u{300}'),
+    ('^', '\u{302}'),
+    ('"', '\u{308}'),
+    ('~', '\u{303}'),
+    (',', '\u{327}'),
+    ('*', '\u{30a}'),
+    ('<', '\u{30c}'),
+    ('-', '\u{304}'),
+    ('(', '\u{306}'),
+    ('.', '\u{307}'),
+    (';', '\u{328}'),
+    ('=', '\u{30b}'),
+];
+
+/// Sequences which are not letter with mark.
+const OTHER: &[([char; 2], char)] = &[
+    (['s', 's'], 'ß'),
+    (['a', 'e'], 'æ'),
+    (['A', 'E'], 'Æ'),
+    (['o', 'e'], 'œ'),
+    (['O', 'E'], 'Œ'),
+    (['o', '/'], 'ø'),
+    (['O', '/'], 'Ø'),
+    (['!', '!'], '¡'),
+    (['?', '?'], '¿'),
+    (['<', '<'], '«'),
+    (['>', '>'], '»'),
+];
+
+/// Char composed of `a` and `b` in any order, [`ComposeOptions::sequences`] first.
+pub(crate) fn compose(a: char, b: char, options: &ComposeOptions) -> Option<char> {
+    let find = |a, b| {
+        let mut sequences = options.sequences.iter().chain(OTHER);
+        sequences
+            .find(|(x, _)| *x == [a, b])
+            .map(|&(_, c)| c)
+            .or_else(|| compose_mark(a, b))
+    };
+    find(a, b).or_else(|| find(b, a))
+}
+
+/// Letter `b` with mark typed as `a`.
+#[cfg(feature = "unicode-normalization")]
+fn compose_mark(a: char, b: char) -> Option<char> {
+    let mark = MARKS.iter().find(|&&(x, _)| x == a)?.1;
+    unicode_normalization::char::compose(b, mark)
+}
+#[cfg(not(feature = "unicode-normalization"))]
+fn compose_mark(_: char, _: char) -> Option<char> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences_in_any_order() {
+        let options = ComposeOptions {
+            sequences: vec![(['s', 's'], '§')],
+            ..Default::default()
+        };
+        assert_eq!(compose('s', 's', &options), Some('§'));
+        assert_eq!(compose('e', 'a', &options), Some('æ'));
+        assert_eq!(compose('x', 'y', &options), None);
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn letters_with_marks() {
+        let options = ComposeOptions::default();
+        assert_eq!(compose('\'', 'e', &options), Some('é'));
+        assert_eq!(compose('Z', '<', &options), Some('Ž'));
+        assert_eq!(compose('(', 'и', &options), Some('й'));
+        assert_eq!(compose('\'', 'x', &options), None);
+    }
+}
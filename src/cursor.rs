@@ -0,0 +1,64 @@
+use crossterm::cursor::SetCursorStyle;
+use ratatui::layout::{Position, Rect};
+
+/// How cursor of focused text input is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorMode {
+    /// `|` drawn into text.
+    #[default]
+    Drawn,
+    /// Nothing is drawn, caller places terminal cursor at [`CursorPosition`].
+    Terminal(CursorShape),
+}
+impl CursorMode {
+    pub(crate) fn is_drawn(self) -> bool {
+        self == CursorMode::Drawn
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorShape {
+    #[default]
+    Bar,
+    Block,
+    Underline,
+}
+impl CursorShape {
+    /// Command setting this shape, execute it with `crossterm::execute!` before input and
+    /// restore with [`SetCursorStyle::DefaultUserShape`] after it.
+    pub fn command(self, blinking: bool) -> SetCursorStyle {
+        match (self, blinking) {
+            (CursorShape::Bar, true) => SetCursorStyle::BlinkingBar,
+            (CursorShape::Bar, false) => SetCursorStyle::SteadyBar,
+            (CursorShape::Block, true) => SetCursorStyle::BlinkingBlock,
+            (CursorShape::Block, false) => SetCursorStyle::SteadyBlock,
+            (CursorShape::Underline, true) => SetCursorStyle::BlinkingUnderScore,
+            (CursorShape::Underline, false) => SetCursorStyle::SteadyUnderScore,
+        }
+    }
+}
+
+/// Position of cursor in rendered text, if it's rendered without wrapping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CursorPosition {
+    /// Index of line.
+    pub line: usize,
+    /// Width of text before cursor in terminal cells.
+    pub column: usize,
+}
+impl CursorPosition {
+    /// Position on screen if text is rendered in `area` scrolled down by `scroll` lines.
+    ///
+    /// Use it with `Frame::set_cursor_position`.
+    ///
+    /// # Returns
+    /// `None` if cursor is outside of `area`
+    pub fn on_screen(self, area: Rect, scroll: u16) -> Option<Position> {
+        let line = u16::try_from(self.line).ok()?.checked_sub(scroll)?;
+        let column = u16::try_from(self.column).ok()?;
+        let position = Position::new(area.x.checked_add(column)?, area.y.checked_add(line)?);
+        area.contains(position).then_some(position)
+    }
+}
@@ -0,0 +1,94 @@
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// In both sequences
+    Equal,
+    /// Only in first sequence
+    Removed,
+    /// Only in second sequence
+    Added,
+}
+
+/// Shortest edit script turning `a` into `b`, based on longest common subsequence.
+///
+/// # Returns
+/// Runs of same [`DiffKind`] with range of items in `a`(for `Equal` and `Removed`) or in
+/// `b`(for `Added`).
+pub fn diff<T: PartialEq>(a: &[T], b: &[T]) -> Vec<(DiffKind, Range<usize>)> {
+    // lcs[i][j] is length of longest common subsequence of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut runs: Vec<(DiffKind, Range<usize>)> = Vec::new();
+    let mut push = |kind, index| match runs.last_mut() {
+        Some((last_kind, range)) if *last_kind == kind && range.end == index => range.end += 1,
+        _ => runs.push((kind, index..index + 1)),
+    };
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            push(DiffKind::Equal, i);
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            push(DiffKind::Removed, i);
+            i += 1;
+        } else {
+            push(DiffKind::Added, j);
+            j += 1;
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffKind::*, *};
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(diff::<char>(&[], &[]), []);
+        assert_eq!(diff(&chars("ab"), &chars("ab")), [(Equal, 0..2)]);
+    }
+
+    #[test]
+    fn all_added_or_removed() {
+        assert_eq!(diff(&[], &chars("abc")), [(Added, 0..3)]);
+        assert_eq!(diff(&chars("abc"), &[]), [(Removed, 0..3)]);
+        assert_eq!(
+            diff(&chars("ab"), &chars("cd")),
+            [(Removed, 0..2), (Added, 0..2)]
+        );
+    }
+
+    #[test]
+    fn runs_of_changes() {
+        assert_eq!(
+            diff(&chars("colour"), &chars("color")),
+            [(Equal, 0..4), (Removed, 4..5), (Equal, 5..6)]
+        );
+        assert_eq!(
+            diff(&chars("été"), &chars("ete")),
+            [
+                (Removed, 0..1),
+                (Added, 0..1),
+                (Equal, 1..2),
+                (Removed, 2..3),
+                (Added, 2..3)
+            ]
+        );
+    }
+}
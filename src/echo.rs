@@ -0,0 +1,30 @@
+/// How typed text is displayed, e.g. [`Masked`](Self::Masked) for passwords.
+///
+/// Real text is kept and returned as usual.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Echo {
+    #[default]
+    Plain,
+    /// Each char is displayed as given one, e.g. `•`.
+    Masked(char),
+    /// Nothing is displayed.
+    Hidden,
+}
+impl Echo {
+    /// Masked with `•`.
+    pub fn password() -> Self {
+        Echo::Masked('•')
+    }
+    /// Text as displayed, line breaks are kept.
+    pub(crate) fn apply(self, text: &str) -> String {
+        match self {
+            Echo::Plain => text.to_owned(),
+            Echo::Masked(mask) => text
+                .chars()
+                .map(|c| if c == '\n' { c } else { mask })
+                .collect(),
+            Echo::Hidden => text.chars().filter(|&c| c == '\n').collect(),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crossterm::event::{KeyCode, KeyEventKind};
+
+/// Action chosen by user after answer is displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnswerAction {
+    Continue,
+    Retry,
+    Quit,
+    /// Mark question for review
+    Flag,
+}
+
+/// Prompt rendered under answer by [`display_answer_with`](crate::display_answer_with).
+#[derive(Debug, Clone)]
+pub struct EndPrompt {
+    /// Rendered text, [`Labels::end_prompt`](crate::Labels::end_prompt) if `None`.
+    pub text: Option<String>,
+    /// Keys and actions they choose.
+    pub keys: Vec<(KeyCode, AnswerAction)>,
+    /// Action chosen by any other key, `None` to ignore other keys.
+    pub any_key: Option<AnswerAction>,
+}
+impl Default for EndPrompt {
+    fn default() -> Self {
+        Self {
+            text: None,
+            keys: vec![
+                (KeyCode::Enter, AnswerAction::Continue),
+                (KeyCode::Char(' '), AnswerAction::Continue),
+                (KeyCode::Char('r'), AnswerAction::Retry),
+                (KeyCode::Char('f'), AnswerAction::Flag),
+                (KeyCode::Char('q'), AnswerAction::Quit),
+                (KeyCode::Esc, AnswerAction::Quit),
+            ],
+            any_key: None,
+        }
+    }
+}
+impl EndPrompt {
+    /// Prompt accepting only Enter.
+    pub fn continue_only(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            keys: vec![(KeyCode::Enter, AnswerAction::Continue)],
+            any_key: None,
+        }
+    }
+    /// Prompt where any key continues.
+    pub fn any_key(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            keys: vec![],
+            any_key: Some(AnswerAction::Continue),
+        }
+    }
+    /// `on_key` gets keys first, if it returns `true` key is consumed and prompt is redrawn.
+    pub(crate) fn get_action(
+        &self,
+        on_key: &mut impl FnMut(KeyCode) -> bool,
+        render: &mut impl FnMut() -> std::io::Result<()>,
+    ) -> std::io::Result<AnswerAction> {
+        loop {
+            render()?;
+            loop {
+                match crossterm::event::read()? {
+                    crossterm::event::Event::Key(k)
+                        if k.kind == KeyEventKind::Press && on_key(k.code) =>
+                    {
+                        break
+                    }
+                    crossterm::event::Event::Key(k) if k.kind == KeyEventKind::Press => {
+                        let action = self
+                            .keys
+                            .iter()
+                            .find(|(key, _)| *key == k.code)
+                            .map(|(_, action)| *action)
+                            .or(self.any_key);
+                        if let Some(action) = action {
+                            return Ok(action);
+                        }
+                    }
+                    crossterm::event::Event::Resize(_, _) => break,
+                    _ => (),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::{Options, Theme};
+
+/// Explanation rendered dimmed under answered block.
+///
+/// Supports simple markup: `**bold**` and `*italic*`, `\` escapes next char. Markers are kept as
+/// text unless they are paired on the same line, opening one followed and closing one preceded
+/// by non-whitespace, so `2 * 3 = 6` is displayed as is.
+pub(crate) fn as_lines(explanation: &str, theme: &Theme) -> Vec<Line<'static>> {
+    explanation
+        .lines()
+        .map(|line| {
+            let mut spans = vec![Span::raw("    ")];
+            spans.extend(parse_markup(line));
+            Line::from(spans).style(theme.dimmed)
+        })
+        .collect()
+}
+
+/// Shown instead of explanation when it's hidden.
+pub(crate) fn hidden_line(options: &Options) -> Line<'static> {
+    Line::styled(
+        format!("    {}", options.labels.explanation_hidden),
+        options.theme.prompt,
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Token {
+    Char(char),
+    /// `*` or `**`.
+    Marker(Modifier),
+}
+impl Token {
+    fn is_whitespace(self) -> bool {
+        matches!(self, Token::Char(c) if c.is_whitespace())
+    }
+}
+
+fn tokenize(line: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '\\' => Token::Char(chars.next().unwrap_or(c)),
+            '*' if chars.next_if_eq(&'*').is_some() => Token::Marker(Modifier::BOLD),
+            '*' => Token::Marker(Modifier::ITALIC),
+            c => Token::Char(c),
+        });
+    }
+    tokens
+}
+
+/// # Returns
+/// whether each token is paired marker
+fn pair_markers(tokens: &[Token]) -> Vec<bool> {
+    let mut paired = vec![false; tokens.len()];
+    let mut open_bold = None;
+    let mut open_italic = None;
+    for (i, token) in tokens.iter().enumerate() {
+        let open = match token {
+            Token::Marker(Modifier::BOLD) => &mut open_bold,
+            Token::Marker(_) => &mut open_italic,
+            Token::Char(_) => continue,
+        };
+        let can_open = tokens.get(i + 1).is_some_and(|x| !x.is_whitespace());
+        let can_close = i > 0 && !tokens[i - 1].is_whitespace();
+        match *open {
+            Some(start) if can_close => {
+                paired[start] = true;
+                paired[i] = true;
+                *open = None;
+            }
+            None if can_open => *open = Some(i),
+            _ => (),
+        }
+    }
+    paired
+}
+
+fn parse_markup(line: &str) -> Vec<Span<'static>> {
+    let tokens = tokenize(line);
+    let paired = pair_markers(&tokens);
+    let mut spans = Vec::new();
+    let mut style = Style::new();
+    let mut buffer = String::new();
+    for (token, paired) in tokens.into_iter().zip(paired) {
+        match token {
+            Token::Char(c) => buffer.push(c),
+            Token::Marker(modifier) if !paired => {
+                buffer.push_str(if modifier == Modifier::BOLD {
+                    "**"
+                } else {
+                    "*"
+                });
+            }
+            Token::Marker(modifier) => {
+                if !buffer.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut buffer), style));
+                }
+                style = Style::new().add_modifier(style.add_modifier ^ modifier);
+            }
+        }
+    }
+    if !buffer.is_empty() {
+        spans.push(Span::styled(buffer, style));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(line: &str) -> Vec<(String, Modifier)> {
+        parse_markup(line)
+            .into_iter()
+            .map(|x| (x.content.into_owned(), x.style.add_modifier))
+            .collect()
+    }
+    fn plain(line: &str) -> Vec<(String, Modifier)> {
+        vec![(line.to_owned(), Modifier::empty())]
+    }
+
+    #[test]
+    fn bold_and_italic() {
+        assert_eq!(
+            parsed("**bold** and *italic*"),
+            [
+                ("bold".to_owned(), Modifier::BOLD),
+                (" and ".to_owned(), Modifier::empty()),
+                ("italic".to_owned(), Modifier::ITALIC),
+            ]
+        );
+        assert_eq!(
+            parsed("***both***"),
+            [("both".to_owned(), Modifier::BOLD | Modifier::ITALIC)]
+        );
+    }
+
+    #[test]
+    fn escaped_markers() {
+        assert_eq!(parsed(r"\*not italic\*"), plain("*not italic*"));
+        assert_eq!(parsed(r"a \\ b\"), plain(r"a \ b\"));
+    }
+
+    #[test]
+    fn unpaired_markers_are_text() {
+        assert_eq!(parsed("2 * 3 = 6"), plain("2 * 3 = 6"));
+        assert_eq!(parsed("2 * 3 * 4"), plain("2 * 3 * 4"));
+        assert_eq!(parsed("*open"), plain("*open"));
+        assert_eq!(parsed("**open *italic"), plain("**open *italic"));
+        assert_eq!(parsed("**a *b**"), [("a *b".to_owned(), Modifier::BOLD)]);
+    }
+}
@@ -0,0 +1,30 @@
+use std::io::Write;
+
+use crossterm::event::{
+    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+
+/// Enables keyboard enhancement protocol if terminal supports it, so keys are reported
+/// unambiguously and held keys repeat reliably. Call it after entering raw mode and
+/// [`disable_keyboard_enhancement`] before leaving it.
+///
+/// # Returns
+/// `false` if terminal doesn't support it
+pub fn enable_keyboard_enhancement(out: &mut impl Write) -> std::io::Result<bool> {
+    if !crossterm::terminal::supports_keyboard_enhancement()? {
+        return Ok(false);
+    }
+    crossterm::execute!(
+        out,
+        PushKeyboardEnhancementFlags(
+            KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+        )
+    )?;
+    Ok(true)
+}
+
+/// Restores keyboard reporting changed by [`enable_keyboard_enhancement`].
+pub fn disable_keyboard_enhancement(out: &mut impl Write) -> std::io::Result<()> {
+    crossterm::execute!(out, PopKeyboardEnhancementFlags)
+}
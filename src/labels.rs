@@ -0,0 +1,208 @@
+use std::fmt::Display;
+
+/// User visible text, see [`Options::labels`](crate::Options::labels).
+///
+/// Each `{}` in template is replaced with corresponding argument, described in field docs, and
+/// `{0}`, `{1}` and so on with argument at that position, so translations can reorder them.
+/// Default is English, for translation override fields:
+/// ```
+/// let labels = ratatui_inputs::Labels {
+///     empty: "<leer>".to_owned(),
+///     parse_error: "Fehler: {}.".to_owned(),
+///     block_number: "{1} Blöcke, Block {0}.".to_owned(),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Labels {
+    /// Empty blank field.
+    pub empty: String,
+    /// Blank field without user answer in answered block.
+    pub not_answered_blank: String,
+    /// `one of` block without user answer in answered block.
+    pub not_answered: String,
+    /// Prefix of `one of` items with [`OneOfPrefix::Bullet`](crate::OneOfPrefix::Bullet).
+    pub bullet: String,
+    /// Template with other accepted answers.
+    pub also_accepted: String,
+    /// Template with time left, e.g. `1:05`.
+    pub time_left: String,
+    /// Shown instead of hidden explanation.
+    pub explanation_hidden: String,
+    /// Template with error of blank field, shown next to it.
+    pub invalid_blank: String,
+    /// Error of [`Numeric`](crate::validator::Numeric).
+    pub not_a_number: String,
+    /// Template with minimum and maximum, error of [`IntRange`](crate::validator::IntRange).
+    pub not_in_range: String,
+    /// Template with maximum, error of [`MaxLength`](crate::validator::MaxLength).
+    pub too_long: String,
+    /// Error of [`SingleWord`](crate::validator::SingleWord).
+    pub not_single_word: String,
+    /// Error of `validator::Regex` without own message.
+    pub not_matching: String,
+    /// Template with [`BlankOptions::mask`](crate::BlankOptions::mask), error of incomplete
+    /// field.
+    pub mask_incomplete: String,
+    /// Default text of [`EndPrompt`](crate::EndPrompt).
+    pub end_prompt: String,
+    /// Template with number of `one of` item, see
+    /// [`OneOfPrefix::Numbers`](crate::OneOfPrefix::Numbers).
+    pub number_prefix: String,
+    /// Template with letter of `one of` item, see
+    /// [`OneOfPrefix::Letters`](crate::OneOfPrefix::Letters).
+    pub letter_prefix: String,
+    /// Template with hint shown next to blank field, see [`Options::hint`](crate::Options::hint).
+    pub hint: String,
+    /// Shown in place of char typed after compose key, see
+    /// [`Options::compose`](crate::Options::compose).
+    pub composing: String,
+    /// Template with number of correct items, number of all items and percent, see
+    /// [`Score::summary_with`](crate::Score::summary_with).
+    pub summary: String,
+    /// Template with parse error, used by [`get_block_with`](crate::get_block_with) and
+    /// [`get_blocks_with`](crate::get_blocks_with).
+    pub parse_error: String,
+    /// Template with preview of parsed blocks, used by
+    /// [`get_block_with`](crate::get_block_with) and [`get_blocks_with`](crate::get_blocks_with)
+    /// as others below.
+    pub parsed: String,
+    /// Template with correct answer of blank field.
+    pub parsed_blank: String,
+    /// Template with correct item of `one of` block.
+    pub parsed_correct_item: String,
+    /// Template with other item of `one of` block.
+    pub parsed_item: String,
+    /// Template with correct item of `any of` block.
+    pub parsed_selected_item: String,
+    /// Template with other item of `any of` block.
+    pub parsed_not_selected_item: String,
+
+    /// Template with number of block and number of all blocks, used by
+    /// [`get_input_linear`](crate::get_input_linear) as others below.
+    pub block_number: String,
+    /// Template with number of items.
+    pub choose_one_of: String,
+    /// Template with number of item and item, also used for `order` blocks in preview of parsed
+    /// blocks.
+    pub numbered_item: String,
+    /// Template with number of selected item.
+    pub current_answer: String,
+    pub number_prompt: String,
+    /// Template with number of items.
+    pub invalid_number: String,
+    /// Template with answer.
+    pub your_answer: String,
+    /// Template with paragraph.
+    pub fill_blanks: String,
+    /// Template with number of blank field, shown in place of it.
+    pub blank: String,
+    /// Template with number of blank field and its answer.
+    pub current_blank_answer: String,
+    /// Template with number of blank field.
+    pub blank_prompt: String,
+    pub answer_required: String,
+    pub confirm: String,
+    /// Answer confirming, its prefixes(e.g. `y`) and empty answer confirm as well.
+    pub yes: String,
+    /// Answer rejecting, its prefixes reject as well.
+    pub no: String,
+    pub invalid_confirmation: String,
+}
+impl Default for Labels {
+    fn default() -> Self {
+        Self {
+            empty: "<empty>".to_owned(),
+            not_answered_blank: "<not answered>".to_owned(),
+            not_answered: "not answered".to_owned(),
+            bullet: " -  ".to_owned(),
+            also_accepted: " (also accepted: {})".to_owned(),
+            time_left: "Time left: {}".to_owned(),
+            explanation_hidden: "(explanation hidden)".to_owned(),
+            invalid_blank: " ! {}".to_owned(),
+            not_a_number: "Enter a number.".to_owned(),
+            not_in_range: "Enter a whole number from {} to {}.".to_owned(),
+            too_long: "Enter at most {} characters.".to_owned(),
+            not_single_word: "Enter a single word.".to_owned(),
+            not_matching: "Enter text in the required format.".to_owned(),
+            mask_incomplete: "Enter text in format {}.".to_owned(),
+            end_prompt: "Press Enter to continue, r to retry, f to flag, q to quit".to_owned(),
+            number_prefix: " {}. ".to_owned(),
+            letter_prefix: " {}) ".to_owned(),
+            hint: " ({})".to_owned(),
+            composing: "◌".to_owned(),
+            summary: "{}/{} correct, {}%".to_owned(),
+            parse_error: "Error: {}.".to_owned(),
+            parsed: "{}".to_owned(),
+            parsed_blank: "[{}]".to_owned(),
+            parsed_correct_item: "(x) {}".to_owned(),
+            parsed_item: "( ) {}".to_owned(),
+            parsed_selected_item: "[x] {}".to_owned(),
+            parsed_not_selected_item: "[ ] {}".to_owned(),
+            block_number: "Block {} of {}.".to_owned(),
+            choose_one_of: "Choose one of {} options:".to_owned(),
+            numbered_item: "{}. {}".to_owned(),
+            current_answer: "Current answer: {}.".to_owned(),
+            number_prompt: "Number: ".to_owned(),
+            invalid_number: "Enter number from 1 to {}.".to_owned(),
+            your_answer: "Your answer: {}.".to_owned(),
+            fill_blanks: "Fill in the blanks: {}".to_owned(),
+            blank: "[blank {}]".to_owned(),
+            current_blank_answer: "Current answer for blank {}: {}.".to_owned(),
+            blank_prompt: "Blank {}: ".to_owned(),
+            answer_required: "Answer is required.".to_owned(),
+            confirm: "Confirm answer? [Y/n] ".to_owned(),
+            yes: "yes".to_owned(),
+            no: "no".to_owned(),
+            invalid_confirmation: "Enter y or n.".to_owned(),
+        }
+    }
+}
+
+/// Replaces each `{}` in `template` with next of `args` and each `{n}` with `n`-th of them,
+/// starting from 0. Placeholders without corresponding argument are left as is.
+pub(crate) fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut next = 0;
+    let mut result = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = rest.find('}').map(|end| &rest[..=end]);
+        let arg = placeholder.and_then(|x| match &x[1..x.len() - 1] {
+            "" => {
+                next += 1;
+                args.get(next - 1)
+            }
+            index => args.get(index.parse::<usize>().ok()?),
+        });
+        match (placeholder, arg) {
+            (Some(placeholder), Some(arg)) => {
+                result.push_str(&arg.to_string());
+                rest = &rest[placeholder.len()..];
+            }
+            _ => {
+                result.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_placeholders() {
+        assert_eq!(fill("{}/{} correct", &[&7, &9]), "7/9 correct");
+        assert_eq!(fill("{1} von {0}", &[&"A", &"B"]), "B von A");
+        assert_eq!(fill("{0}: {}, {}", &[&1, &2]), "1: 1, 2");
+        assert_eq!(fill("{} {} {5}", &[&1]), "1 {} {5}");
+        assert_eq!(fill("{x} {{}} {", &[&1]), "{x} {1} {");
+        assert_eq!(fill("{é}", &[]), "{é}");
+    }
+}
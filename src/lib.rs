@@ -2,42 +2,204 @@
 #![warn(clippy::too_many_lines)]
 #![allow(dead_code)] // FIXME: remove this
 
-use ratatui::text::Text;
-use s_text_input_f::Block;
+use ratatui::text::{Line, Text};
 use std::fmt::Write;
 
 pub fn get_input(
     input_request: s_text_input_f::Blocks,
     render: &mut impl FnMut(ratatui::text::Text) -> std::io::Result<()>,
 ) -> Option<std::io::Result<(ResultKind, s_text_input_f::Response)>> {
-    let mut blocks_wrapper = blocks_wrapper::BlocksWrapper::from(input_request);
-    match blocks_wrapper.get_input(render)? {
+    get_input_with(input_request, &Options::default(), render)
+}
+
+pub fn get_input_with(
+    input_request: s_text_input_f::Blocks,
+    options: &Options,
+    render: &mut impl FnMut(ratatui::text::Text) -> std::io::Result<()>,
+) -> Option<std::io::Result<(ResultKind, s_text_input_f::Response)>> {
+    let mut blocks_wrapper = BlocksWrapper::from(input_request);
+    match blocks_wrapper.get_input(options, render)? {
         Ok(result_kind) => Some(Ok((result_kind, blocks_wrapper.finalize()))),
         Err(err) => Some(Err(err)),
     }
 }
 
-// TODO: Create custom handled for end of interaction
+/// Same as [`get_input_with`], but `render` also gets position of cursor in rendered text, so
+/// terminal cursor can be placed there with [`CursorMode::Terminal`].
+///
+/// ```ignore
+/// terminal.draw(|frame| {
+///     frame.render_widget(Paragraph::new(text), area);
+///     if let Some(position) = cursor.on_screen(area, 0) {
+///         frame.set_cursor_position(position);
+///     }
+/// })?;
+/// ```
+pub fn get_input_with_cursor(
+    input_request: s_text_input_f::Blocks,
+    options: &Options,
+    render: &mut impl FnMut(ratatui::text::Text, CursorPosition) -> std::io::Result<()>,
+) -> Option<std::io::Result<(ResultKind, s_text_input_f::Response)>> {
+    let mut blocks_wrapper = BlocksWrapper::from(input_request);
+    match blocks_wrapper.get_input_with_cursor(options, render)? {
+        Ok(result_kind) => Some(Ok((result_kind, blocks_wrapper.finalize()))),
+        Err(err) => Some(Err(err)),
+    }
+}
+
+/// Same as [`get_input_with`], but with line oriented prompts instead of full screen rendering,
+/// e.g. for screen readers, see [`BlocksWrapper::get_input_linear`].
+pub fn get_input_linear(
+    input_request: s_text_input_f::Blocks,
+    options: &Options,
+    input: &mut impl std::io::BufRead,
+    output: &mut impl std::io::Write,
+) -> Option<std::io::Result<(ResultKind, s_text_input_f::Response)>> {
+    let mut blocks_wrapper = BlocksWrapper::from(input_request);
+    match blocks_wrapper.get_input_linear(options, input, output)? {
+        Ok(result_kind) => Some(Ok((result_kind, blocks_wrapper.finalize()))),
+        Err(err) => Some(Err(err)),
+    }
+}
+
+/// Same as [`get_input_with`], but blocks are prefilled with `answers`(e.g. returned by previous
+/// interrupted input), so user can continue or edit them.
+///
+/// To combine it with correct answers or get [`BlockMetrics`], use [`BlocksWrapper`] directly.
+pub fn get_input_prefilled(
+    input_request: s_text_input_f::Blocks,
+    answers: s_text_input_f::Response,
+    options: &Options,
+    render: &mut impl FnMut(ratatui::text::Text) -> std::io::Result<()>,
+) -> Option<std::io::Result<(ResultKind, s_text_input_f::Response)>> {
+    let mut blocks_wrapper = BlocksWrapper::with_answers(input_request, answers);
+    match blocks_wrapper.get_input(options, render)? {
+        Ok(result_kind) => Some(Ok((result_kind, blocks_wrapper.finalize()))),
+        Err(err) => Some(Err(err)),
+    }
+}
+
+/// Same as [`get_input_with`], but with `correct_answer` known up front, so each block can be
+/// checked as soon as user leaves it with [`Options::immediate_feedback`] and user can get
+/// [`Options::hint`].
+///
+/// To combine it with prefilled answers or get [`BlockMetrics`], use [`BlocksWrapper`] directly.
+///
+/// # Returns
+/// Also [`HintsUsed`].
+pub fn get_input_with_answers(
+    input_request: s_text_input_f::Blocks,
+    correct_answer: AcceptedAnswers,
+    options: &Options,
+    render: &mut impl FnMut(ratatui::text::Text) -> std::io::Result<()>,
+) -> Option<std::io::Result<(ResultKind, s_text_input_f::Response, HintsUsed)>> {
+    let mut blocks_wrapper = BlocksWrapper::from(input_request).with_correct_answer(correct_answer);
+    match blocks_wrapper.get_input(options, render)? {
+        Ok(result_kind) => {
+            let hints_used = blocks_wrapper.hints_used();
+            Some(Ok((result_kind, blocks_wrapper.finalize(), hints_used)))
+        }
+        Err(err) => Some(Err(err)),
+    }
+}
+
+/// Displays `user_answer` checked against `correct_answer` and waits for user to choose
+/// [`AnswerAction`] with [`Options::end_prompt`].
+///
+/// Unanswered `one of` blocks(empty vec in `user_answer`) are displayed as not answered.
 pub fn display_answer(
     input_blocks: s_text_input_f::Blocks,
     user_answer: Vec<Vec<String>>,
     correct_answer: Vec<Vec<String>>,
     render: &mut impl FnMut(ratatui::text::Text) -> std::io::Result<()>,
-) -> std::io::Result<()> {
-    let answered = {
-        let mut temp = s_text_input_f::to_answered(input_blocks, user_answer, correct_answer)
-            .into_iter()
-            .map(s_text_input_f::Block::Answered)
-            .collect::<Vec<_>>();
-        temp.push(Block::Paragraph(vec![
-            s_text_input_f::ParagraphItem::Placeholder,
-        ]));
-        temp
-    };
+) -> std::io::Result<AnswerAction> {
+    display_answer_with(
+        input_blocks,
+        user_answer,
+        accept_only(correct_answer),
+        &Options::default(),
+        render,
+    )
+}
 
-    get_input(answered, render)
-        .expect("Last elem must be blank field by design. It's a bug")
-        .map(|_| ())
+/// For each block, for each item of its response: accepted answers. First one is displayed as
+/// correct answer.
+pub type AcceptedAnswers = Vec<Vec<Vec<String>>>;
+
+/// For each block, for each blank field: number of hints used.
+pub type HintsUsed = Vec<Vec<usize>>;
+
+/// Converts response with one correct answer for each item to [`AcceptedAnswers`].
+pub fn accept_only(correct_answer: s_text_input_f::Response) -> AcceptedAnswers {
+    correct_answer
+        .into_iter()
+        .map(|x| x.into_iter().map(|x| vec![x]).collect())
+        .collect()
+}
+
+/// Same as [`display_answer`], but blank fields are checked with [`Options::matchers`] and
+/// can have several accepted answers.
+///
+/// With [`Options::show_summary`] renders [`Score::summary`] under answered blocks.
+pub fn display_answer_with(
+    input_blocks: s_text_input_f::Blocks,
+    user_answer: Vec<Vec<String>>,
+    correct_answer: AcceptedAnswers,
+    options: &Options,
+    render: &mut impl FnMut(ratatui::text::Text) -> std::io::Result<()>,
+) -> std::io::Result<AnswerAction> {
+    display_answer_explained(
+        input_blocks,
+        user_answer,
+        correct_answer,
+        Vec::new(),
+        options,
+        render,
+    )
+}
+
+/// Same as [`display_answer_with`], but with optional explanation rendered dimmed under each
+/// block.
+///
+/// Explanations support simple markup: `**bold**` and `*italic*`, unpaired `*` is displayed
+/// as is and `\` escapes next char. They can be shown and hidden with [`ExplanationOptions::key`].
+pub fn display_answer_explained(
+    input_blocks: s_text_input_f::Blocks,
+    user_answer: Vec<Vec<String>>,
+    correct_answer: AcceptedAnswers,
+    explanations: Vec<Option<String>>,
+    options: &Options,
+    render: &mut impl FnMut(ratatui::text::Text) -> std::io::Result<()>,
+) -> std::io::Result<AnswerAction> {
+    let summary = options.show_summary.then(|| {
+        score(&input_blocks, &user_answer, &correct_answer, options).summary_with(&options.labels)
+    });
+    let answered = BlocksWrapper::answered(
+        input_blocks,
+        user_answer,
+        correct_answer,
+        explanations,
+        &options.matchers,
+        summary,
+    );
+    let has_explanations = answered.has_explanations();
+    let show_explanations = std::cell::Cell::new(!options.explanation.hidden);
+    options.end_prompt.get_action(
+        &mut |key| {
+            let toggle = has_explanations && key == options.explanation.key;
+            if toggle {
+                show_explanations.set(!show_explanations.get());
+            }
+            toggle
+        },
+        &mut || {
+            let mut text = answered.as_text_explained(options, show_explanations.get());
+            let prompt = options.end_prompt.text.as_ref();
+            let prompt = prompt.unwrap_or(&options.labels.end_prompt);
+            text.push_line(Line::styled(prompt.as_str(), options.theme.prompt));
+            render(text)
+        },
+    )
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -46,10 +208,41 @@ pub enum ResultKind {
     Canceled,
     NextBlock,
     PrevBlock,
+    /// Time limit from [`Options`] expired, response contains what was filled in.
+    TimedOut,
 }
 
+pub mod answer_matcher;
 mod blank_field;
+pub mod completion;
+mod compose;
+mod cursor;
+mod diff;
+mod echo;
+mod end_prompt;
+mod explanation;
+mod keyboard;
+mod labels;
+mod metrics;
 mod multiline_input;
+mod options;
+mod per_blank;
+mod score;
+mod theme;
+mod timer;
+pub mod validator;
+pub use cursor::{CursorMode, CursorPosition, CursorShape};
+pub use echo::Echo;
+pub use end_prompt::{AnswerAction, EndPrompt};
+pub use keyboard::{disable_keyboard_enhancement, enable_keyboard_enhancement};
+pub use labels::Labels;
+pub use metrics::BlockMetrics;
+pub use options::{
+    AnswerDiff, BlankOptions, ComposeOptions, ExplanationOptions, HintKind, HintOptions,
+    OneOfPrefix, Options, StartPosition,
+};
+pub use score::{score, BlockScore, Score};
+pub use theme::Theme;
 
 fn split_at_mid<T>(slice: &mut [T], mid: usize) -> Option<(&mut [T], &mut T, &mut [T])> {
     let (head, tail) = slice.split_at_mut(mid);
@@ -58,37 +251,151 @@ fn split_at_mid<T>(slice: &mut [T], mid: usize) -> Option<(&mut [T], &mut T, &mu
 }
 
 mod blocks_wrapper;
+use blocks_wrapper::block_wrapper::paragraph_wrapper::paragraph_item_wrapper::style_active_blank_field;
+pub use blocks_wrapper::BlocksWrapper;
 
+/// Multiline text input, `render` gets styled text and plain text.
+///
+/// Text is styled with default [`Theme`], so `NO_COLOR` environment variable is honoured.
 pub fn get_text_input(
     render: &mut impl FnMut(ratatui::text::Text, String) -> std::io::Result<()>,
 ) -> std::io::Result<(ResultKind, String)> {
-    let mut multyline_input = multiline_input::MultilineInput::default();
+    get_text_input_with_cursor(CursorMode::Drawn, &mut |styled, text, _| {
+        render(styled, text)
+    })
+}
+
+/// Same as [`get_text_input`], but `render` also gets position of cursor in rendered text.
+pub fn get_text_input_with_cursor(
+    cursor: CursorMode,
+    render: &mut impl FnMut(ratatui::text::Text, String, CursorPosition) -> std::io::Result<()>,
+) -> std::io::Result<(ResultKind, String)> {
+    get_text_input_masked(Echo::Plain, cursor, &Theme::default(), render)
+}
+/// Same as [`get_text_input_with_cursor`], but text is displayed according to `echo` in
+/// [`Theme::text_input`] style of `theme`.
+pub fn get_text_input_masked(
+    echo: Echo,
+    cursor: CursorMode,
+    theme: &Theme,
+    render: &mut impl FnMut(ratatui::text::Text, String, CursorPosition) -> std::io::Result<()>,
+) -> std::io::Result<(ResultKind, String)> {
+    let mut multyline_input = multiline_input::MultilineInput::new(echo);
     loop {
-        match multyline_input.get_input(&mut |x| render(x.style(), x.text()))? {
+        match multyline_input.get_input(&mut |x| {
+            render(x.style_with(cursor, theme), x.text(), x.cursor_position())
+        })? {
             ResultKind::Ok => return Ok((ResultKind::Ok, multyline_input.text().to_owned())),
             ResultKind::Canceled => {
                 return Ok((ResultKind::Canceled, multyline_input.text().to_owned()))
             }
             ResultKind::NextBlock => (),
             ResultKind::PrevBlock => (),
+            ResultKind::TimedOut => (),
+        }
+    }
+}
+
+/// Single line input, e.g. login prompt with [`BlankOptions::echo`] set to
+/// [`Echo::password`].
+///
+/// `render` gets rendered line and position of cursor in it, see [`Options::cursor`]. Other used
+/// options are [`Options::theme`] and time limits, when time is out input finishes with
+/// [`ResultKind::TimedOut`].
+pub fn get_line_input(
+    blank: BlankOptions,
+    options: &Options,
+    render: &mut impl FnMut(Line, CursorPosition) -> std::io::Result<()>,
+) -> std::io::Result<(ResultKind, String)> {
+    let mut blank_field = blank_field::BlankField::default();
+    blank_field.set_options(blank);
+    let deadline = options.deadline_from(std::time::Instant::now());
+    loop {
+        let result_kind =
+            blank_field.get_input(None, None, options.compose.as_ref(), deadline, &mut |x| {
+                let spans = style_active_blank_field(x, options);
+                let column = spans[0].width();
+                render(Line::from(spans), CursorPosition { line: 0, column })
+            })?;
+        match result_kind {
+            ResultKind::NextBlock | ResultKind::PrevBlock => (),
+            _ => return Ok((result_kind, blank_field.text())),
         }
     }
 }
 
+/// Plain text preview of parsed `block` with correct `answer`, see [`Labels::parsed`].
+fn describe_block(block: &s_text_input_f::Block, answer: &[String], labels: &Labels) -> String {
+    let is_correct = |i: usize| answer.contains(&i.to_string());
+    let items = |items: &[String], templates: [&str; 2]| {
+        let lines = items.iter().enumerate().map(|(i, x)| {
+            let template = if is_correct(i) {
+                templates[0]
+            } else {
+                templates[1]
+            };
+            labels::fill(template, &[x])
+        });
+        lines.collect::<Vec<_>>().join("\n")
+    };
+    match block {
+        s_text_input_f::Block::Paragraph(paragraph) => {
+            let mut answers = answer.iter();
+            let mut buffer = String::new();
+            for item in paragraph {
+                match item {
+                    s_text_input_f::ParagraphItem::Text(x) => buffer.push_str(x),
+                    s_text_input_f::ParagraphItem::Placeholder => {
+                        let answer = answers.next().map_or("", String::as_str);
+                        buffer.push_str(&labels::fill(&labels.parsed_blank, &[&answer]));
+                    }
+                }
+            }
+            buffer
+        }
+        s_text_input_f::Block::OneOf(x) => {
+            items(x, [&labels.parsed_correct_item, &labels.parsed_item])
+        }
+        s_text_input_f::Block::AnyOf(x) => items(
+            x,
+            [
+                &labels.parsed_selected_item,
+                &labels.parsed_not_selected_item,
+            ],
+        ),
+        s_text_input_f::Block::Order(x) => {
+            let lines = (x.iter().enumerate())
+                .map(|(i, x)| labels::fill(&labels.numbered_item, &[&(i + 1), x]));
+            lines.collect::<Vec<_>>().join("\n")
+        }
+        // parser produces only blocks above
+        _ => String::new(),
+    }
+}
+
+/// Input of block in text format with result of parsing it, styled as [`get_text_input`].
 pub fn get_block(
     render: &mut impl FnMut(Text, String) -> std::io::Result<()>,
+) -> std::io::Result<Option<s_text_input_f_parser::CorrectBlock>> {
+    get_block_with(&Labels::default(), render)
+}
+/// Same as [`get_block`], but with [`Labels::parsed`] and [`Labels::parse_error`] from `labels`.
+pub fn get_block_with(
+    labels: &Labels,
+    render: &mut impl FnMut(Text, String) -> std::io::Result<()>,
 ) -> std::io::Result<Option<s_text_input_f_parser::CorrectBlock>> {
     let (result_kind, inputs) = get_text_input(&mut |styled, text| {
         let support_text = s_text_input_f_parser::parse_block(text.trim())
             .map(|parsed| {
                 let mut buffer = String::new();
-                let _ = writeln!(buffer, "{parsed:#?}");
+                let parsed = describe_block(&parsed.block, &parsed.answer, labels);
+                let _ = writeln!(buffer, "{}", labels::fill(&labels.parsed, &[&parsed]));
                 buffer
             })
             .map_err(|err| {
                 let mut buffer = String::new();
                 for err in err {
-                    let _ = writeln!(buffer, "Error: {err}.");
+                    let _ = writeln!(buffer, "{}", labels::fill(&labels.parse_error, &[&err]));
                 }
                 buffer
             });
@@ -105,20 +412,35 @@ pub fn get_block(
         _ => unreachable!(),
     }
 }
+/// Input of blocks in text format with result of parsing them, styled as [`get_text_input`].
 pub fn get_blocks(
     render: &mut impl FnMut(Text, String) -> std::io::Result<()>,
+) -> std::io::Result<Option<s_text_input_f::BlocksWithAnswer>> {
+    get_blocks_with(&Labels::default(), render)
+}
+/// Same as [`get_blocks`], but with [`Labels::parsed`] and [`Labels::parse_error`] from `labels`.
+pub fn get_blocks_with(
+    labels: &Labels,
+    render: &mut impl FnMut(Text, String) -> std::io::Result<()>,
 ) -> std::io::Result<Option<s_text_input_f::BlocksWithAnswer>> {
     let (result_kind, inputs) = get_text_input(&mut |styled, text| {
         let support_text = s_text_input_f_parser::parse_blocks(text.trim())
             .map(|parsed| {
                 let mut buffer = String::new();
-                let _ = writeln!(buffer, "{parsed:#?}");
+                let parsed = parsed
+                    .blocks
+                    .iter()
+                    .zip(&parsed.answer)
+                    .map(|(block, answer)| describe_block(block, answer, labels))
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                let _ = writeln!(buffer, "{}", labels::fill(&labels.parsed, &[&parsed]));
                 buffer
             })
             .map_err(|err| {
                 let mut buffer = String::new();
                 for err in err {
-                    let _ = writeln!(buffer, "Error: {err}.");
+                    let _ = writeln!(buffer, "{}", labels::fill(&labels.parse_error, &[&err]));
                 }
                 buffer
             });
@@ -135,3 +457,51 @@ pub fn get_blocks(
         _ => unreachable!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s_text_input_f::{Block, ParagraphItem};
+
+    #[test]
+    fn describe_parsed_blocks() {
+        let labels = Labels::default();
+        let strings = |x: &[&str]| x.iter().map(|&x| x.to_owned()).collect::<Vec<_>>();
+        let paragraph = Block::Paragraph(vec![
+            ParagraphItem::Text("The ".to_owned()),
+            ParagraphItem::Placeholder,
+            ParagraphItem::Text(" sat on the ".to_owned()),
+            ParagraphItem::Placeholder,
+            ParagraphItem::Text(".".to_owned()),
+        ]);
+        assert_eq!(
+            describe_block(&paragraph, &strings(&["cat", "mat"]), &labels),
+            "The [cat] sat on the [mat]."
+        );
+        let one_of = Block::OneOf(strings(&["red", "green"]));
+        assert_eq!(
+            describe_block(&one_of, &strings(&["1"]), &labels),
+            "( ) red\n(x) green"
+        );
+        let any_of = Block::AnyOf(strings(&["a", "b", "c"]));
+        assert_eq!(
+            describe_block(&any_of, &strings(&["0", "2"]), &labels),
+            "[x] a\n[ ] b\n[x] c"
+        );
+        let order = Block::Order(strings(&["a", "b"]));
+        assert_eq!(describe_block(&order, &[], &labels), "1. a\n2. b");
+        let labels = Labels {
+            parsed_blank: "<{}>".to_owned(),
+            parsed_correct_item: "{} ✓".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            describe_block(&paragraph, &strings(&["cat"]), &labels),
+            "The <cat> sat on the <>."
+        );
+        assert_eq!(
+            describe_block(&one_of, &strings(&["0"]), &labels),
+            "red ✓\n( ) green"
+        );
+    }
+}
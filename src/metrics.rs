@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+/// How user interacted with one block during input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockMetrics {
+    /// Total time block was focused.
+    pub time_focused: Duration,
+    /// Number of changes of text in blank fields.
+    pub edits: usize,
+    /// Number of times selection in `one of` block changed.
+    pub selection_changes: usize,
+    /// Number of hints used for all blank fields, see [`Options::hint`](crate::Options::hint).
+    pub hints: usize,
+    /// Answer differs from the one block had when user first left it answered.
+    pub changed_after_first_entry: bool,
+}
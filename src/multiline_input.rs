@@ -1,9 +1,7 @@
 use super::ResultKind;
+use crate::{CursorMode, CursorPosition, Echo, Theme};
 use crossterm::event::{KeyCode, KeyEventKind};
-use ratatui::{
-    style::Stylize,
-    text::{Line, Span, Text},
-};
+use ratatui::text::{Line, Span, Text};
 
 #[derive(Default, Clone)]
 #[readonly::make]
@@ -11,6 +9,7 @@ use ratatui::{
 pub struct MultilineInput {
     pub text: Vec<char>,
     pub cursor: usize,
+    pub echo: Echo,
 }
 
 enum Event {
@@ -28,6 +27,12 @@ enum Event {
 }
 
 impl MultilineInput {
+    pub fn new(echo: Echo) -> Self {
+        Self {
+            echo,
+            ..Default::default()
+        }
+    }
     pub fn text(&self) -> String {
         self.text.iter().collect()
     }
@@ -60,7 +65,7 @@ impl MultilineInput {
             loop {
                 if let Some(x) = match crossterm::event::read()? {
                     crossterm::event::Event::Key(k) => {
-                        if k.kind == KeyEventKind::Press {
+                        if matches!(k.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
                             match k.code {
                                 KeyCode::Backspace => Some(Event::RemoveCurrentChar),
                                 KeyCode::Enter => {
@@ -128,16 +133,36 @@ impl MultilineInput {
             }
         }
     }
-    pub fn style(&self) -> Text {
+    pub fn style(&self, theme: &Theme) -> Text<'_> {
+        self.style_with(CursorMode::Drawn, theme)
+    }
+    /// Position of cursor in text returned by [`style_with`](Self::style_with).
+    pub fn cursor_position(&self) -> CursorPosition {
+        let before_cursor = &self.text[..self.cursor];
+        let line_start = before_cursor
+            .iter()
+            .rposition(|ch| *ch == '\n')
+            .map_or(0, |x| x + 1);
+        CursorPosition {
+            line: before_cursor.iter().filter(|ch| **ch == '\n').count(),
+            column: Span::raw(
+                self.echo
+                    .apply(&before_cursor[line_start..].iter().collect::<String>()),
+            )
+            .width(),
+        }
+    }
+    /// Text in [`Theme::text_input`] style with cursor in [`Theme::cursor`] style.
+    pub fn style_with(&self, cursor: CursorMode, theme: &Theme) -> Text<'_> {
         let chars = &self.text;
         let (a, b) = chars.split_at(self.cursor);
         let a = a
             .split(|ch| *ch == '\n')
-            .map(|line| line.iter().collect::<String>())
+            .map(|line| self.echo.apply(&line.iter().collect::<String>()))
             .collect::<Vec<_>>();
         let b = b
             .split(|ch| *ch == '\n')
-            .map(|line| line.iter().collect::<String>())
+            .map(|line| self.echo.apply(&line.iter().collect::<String>()))
             .collect::<Vec<_>>();
 
         let mut lines = vec![];
@@ -147,16 +172,24 @@ impl MultilineInput {
                 &mut start
                     .iter()
                     .cloned()
-                    .map(|x| Line::raw(x).italic())
+                    .map(|x| Line::styled(x, theme.text_input))
                     .collect(),
             );
-            current_line.push(Span::raw(mid_left.to_owned()).italic());
+            current_line.push(Span::styled(mid_left.to_owned(), theme.text_input));
+        }
+        if cursor.is_drawn() {
+            current_line.push(Span::styled("|", theme.cursor));
         }
-        current_line.push(Span::raw("|").blue());
         if let Some((mid_right, end)) = b.split_first() {
-            current_line.push(Span::raw(mid_right.to_owned()).italic());
+            current_line.push(Span::styled(mid_right.to_owned(), theme.text_input));
             lines.push(Line::from(current_line));
-            lines.append(&mut end.iter().cloned().map(|x| Line::raw(x).italic()).collect())
+            lines.append(
+                &mut end
+                    .iter()
+                    .cloned()
+                    .map(|x| Line::styled(x, theme.text_input))
+                    .collect(),
+            )
         } else {
             lines.push(Line::from(current_line));
         }
@@ -164,3 +197,48 @@ impl MultilineInput {
         Text::from(lines)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Style, Stylize};
+
+    use super::*;
+    use crate::CursorShape;
+
+    #[test]
+    fn styled_with_theme() {
+        let mut input = MultilineInput::new(Echo::Plain);
+        "ab\ncd".chars().for_each(|c| input.add_char(c));
+        input.move_cursor_left();
+        let theme = Theme::colored();
+        assert_eq!(theme.text_input, Style::new().italic());
+        let text = input.style_with(CursorMode::Drawn, &theme);
+        assert_eq!(text.lines.len(), 2);
+        assert_eq!(text.lines[0].style, theme.text_input);
+        let spans = &text.lines[1].spans;
+        assert_eq!(spans[0].content, "c");
+        assert_eq!(spans[0].style, theme.text_input);
+        assert_eq!(spans[1].content, "|");
+        assert_eq!(spans[1].style, theme.cursor);
+        let text = input.style_with(CursorMode::Terminal(CursorShape::Bar), &theme);
+        assert!(text.lines[1]
+            .spans
+            .iter()
+            .all(|x| x.style == theme.text_input));
+    }
+
+    #[test]
+    fn masked_with_theme() {
+        let mut input = MultilineInput::new(Echo::Masked('*'));
+        "pin".chars().for_each(|c| input.add_char(c));
+        let theme = Theme::colored();
+        let text = input.style_with(CursorMode::Drawn, &theme);
+        let spans = &text.lines[0].spans;
+        assert_eq!(spans[0].content, "***");
+        assert_eq!(spans[0].style, theme.text_input);
+        assert_eq!(
+            input.cursor_position(),
+            CursorPosition { line: 0, column: 3 }
+        );
+    }
+}
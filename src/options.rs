@@ -0,0 +1,310 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    answer_matcher::Matchers, completion::Completers, labels::fill, validator::Validators,
+    CursorMode, Echo, EndPrompt, Labels, Theme,
+};
+
+/// Settings for interactive input.
+///
+/// Construct with struct update syntax:
+/// ```
+/// let options = ratatui_inputs::Options {
+///     one_of_prefix: ratatui_inputs::OneOfPrefix::Numbers,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// How items of `one of` blocks are prefixed and which keys select them.
+    pub one_of_prefix: OneOfPrefix,
+    /// Submit `one of` block right after item is selected with its shortcut key.
+    pub submit_on_shortcut: bool,
+    /// Where focus is placed when input starts.
+    pub start_position: StartPosition,
+    /// Don't finish with [`ResultKind::Ok`](crate::ResultKind::Ok) until every blank field is
+    /// filled and every `one of` block has selection; focus first unanswered input instead.
+    pub require_all_answered: bool,
+    /// How answers for blank fields are checked by [`display_answer_with`](crate::display_answer_with).
+    pub matchers: Matchers,
+    /// Give partial credit for wrong blank fields depending on edit distance to correct answer.
+    pub fuzzy_credit: bool,
+    /// Render [`Score::summary`](crate::Score::summary) under answered blocks.
+    pub show_summary: bool,
+    /// How wrong answers for blank fields are compared with correct ones.
+    pub answer_diff: AnswerDiff,
+    /// Check and lock answered block when user leaves it forward, displaying it as answered.
+    /// Unanswered blocks are skipped, so they can be answered later.
+    ///
+    /// Requires correct answers, see [`get_input_with_answers`](crate::get_input_with_answers).
+    pub immediate_feedback: bool,
+    /// Key revealing part of correct answer for focused blank field, `None` to disable hints.
+    ///
+    /// Requires correct answers, see [`get_input_with_answers`](crate::get_input_with_answers).
+    pub hint: Option<HintOptions>,
+    /// Prompt rendered under answer by [`display_answer_with`](crate::display_answer_with).
+    pub end_prompt: EndPrompt,
+    /// How explanations are shown by
+    /// [`display_answer_explained`](crate::display_answer_explained).
+    pub explanation: ExplanationOptions,
+    /// Styles used for rendering.
+    pub theme: Theme,
+    /// How cursor of focused blank field is shown.
+    pub cursor: CursorMode,
+    /// User visible text.
+    pub labels: Labels,
+    /// Time limit for one input, e.g. one question, counted from start of
+    /// [`get_input_with`](crate::get_input_with).
+    ///
+    /// When time is out input finishes with [`ResultKind::TimedOut`](crate::ResultKind::TimedOut).
+    pub time_limit: Option<Duration>,
+    /// Deadline shared by several inputs, e.g. whole exam session.
+    ///
+    /// Same as [`time_limit`](Self::time_limit), the earlier one is used.
+    pub deadline: Option<Instant>,
+    /// Settings of single blank fields by index of block and index of blank field in it, both
+    /// starting from 0.
+    pub blanks: HashMap<(usize, usize), BlankOptions>,
+    /// Checks of blank fields, input doesn't finish with
+    /// [`ResultKind::Ok`](crate::ResultKind::Ok) until they pass.
+    pub validators: Validators,
+    /// Suggestions for focused blank field, navigated with `Up` and `Down` and accepted with
+    /// `Tab` or `Enter`.
+    pub completers: Completers,
+    /// Key starting composition of accented char in blank fields, `None` to disable it.
+    pub compose: Option<ComposeOptions>,
+}
+impl Options {
+    /// Earlier of [`deadline`](Self::deadline) and [`time_limit`](Self::time_limit) counted
+    /// from `start`.
+    pub(crate) fn deadline_from(&self, start: Instant) -> Option<Instant> {
+        let time_limit = self.time_limit.map(|x| start + x);
+        match (self.deadline, time_limit) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OneOfPrefix {
+    /// [`Labels::bullet`](crate::Labels::bullet), ` -  ` by default, before each item, no
+    /// shortcuts.
+    #[default]
+    Bullet,
+    /// `1.`, `2.`, ... before each item; keys `1`-`9` select corresponding item.
+    ///
+    /// Items after 9th are numbered too, but can be selected only with navigation keys.
+    Numbers,
+    /// `a)`, `b)`, ... before each item; letter keys select corresponding item.
+    ///
+    /// `j`, `k` and `q` are skipped, so they keep navigating and canceling. Items after 23rd
+    /// get [`Labels::bullet`](crate::Labels::bullet) and no shortcut.
+    Letters,
+}
+
+/// Letters of [`OneOfPrefix::Letters`], without `j`, `k` and `q`.
+const SHORTCUT_LETTERS: &[u8] = b"abcdefghilmnoprstuvwxyz";
+impl OneOfPrefix {
+    pub(crate) fn label(self, index: usize, labels: &Labels) -> String {
+        match self {
+            OneOfPrefix::Bullet => labels.bullet.clone(),
+            OneOfPrefix::Numbers => fill(&labels.number_prefix, &[&(index + 1)]),
+            OneOfPrefix::Letters => match SHORTCUT_LETTERS.get(index) {
+                Some(&c) => fill(&labels.letter_prefix, &[&char::from(c)]),
+                None => labels.bullet.clone(),
+            },
+        }
+    }
+    /// # Returns
+    /// index of item selected by `key`
+    pub(crate) fn shortcut(self, key: char) -> Option<usize> {
+        match self {
+            OneOfPrefix::Bullet => None,
+            OneOfPrefix::Numbers => match key {
+                '1'..='9' => Some(key as usize - '1' as usize),
+                _ => None,
+            },
+            OneOfPrefix::Letters => {
+                let key = u8::try_from(key.to_ascii_lowercase()).ok()?;
+                SHORTCUT_LETTERS.iter().position(|&c| c == key)
+            }
+        }
+    }
+}
+
+/// Where focus is placed when input starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StartPosition {
+    #[default]
+    First,
+    Last,
+    /// `placeholder`-th input(blank field or `one of` item) of `block`-th block, both starting from 0.
+    ///
+    /// Falls back to [`First`](StartPosition::First) if there is no such input.
+    At {
+        block: usize,
+        placeholder: usize,
+    },
+    /// First empty blank field or `one of` block without selection.
+    ///
+    /// Falls back to [`First`](StartPosition::First) if everything is answered.
+    FirstUnanswered,
+    /// Input focused when previous [`BlocksWrapper::get_input`](crate::BlocksWrapper::get_input)
+    /// returned, e.g. after restoring serialized state.
+    ///
+    /// Same as [`First`](StartPosition::First) for input that never got focus.
+    Resume,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnswerDiff {
+    /// Whole user answer crossed out, followed by correct answer.
+    #[default]
+    Whole,
+    /// Matching chars, extra chars of user answer and missing chars highlighted separately.
+    Chars,
+    /// Same as [`Chars`](AnswerDiff::Chars), but for words.
+    Words,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HintOptions {
+    pub key: KeyCode,
+    pub modifiers: KeyModifiers,
+    pub kind: HintKind,
+}
+impl Default for HintOptions {
+    /// Ctrl+T reveals next letter.
+    fn default() -> Self {
+        Self {
+            key: KeyCode::Char('t'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: HintKind::NextLetter,
+        }
+    }
+}
+impl HintOptions {
+    pub(crate) fn matches(&self, key: &KeyEvent) -> bool {
+        key.code == self.key && key.modifiers == self.modifiers
+    }
+}
+
+/// Compose key: after it two typed chars are replaced with one, e.g. `'` and `e` with `é`.
+///
+/// Chars can be typed in any order. Marks are `'` acute, `` ` `` grave, `^` circumflex, `"`
+/// diaeresis, `~` tilde, `,` cedilla, `*` ring, `<` caron, `-` macron, `(` breve, `.` dot,
+/// `;` ogonek and `=` double acute, other sequences are `ss` for `ß`, `ae`, `oe`, `o/`, `!!`,
+/// `??`, `<<` and `>>`. Letters with marks need `unicode-normalization` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeOptions {
+    pub key: KeyCode,
+    pub modifiers: KeyModifiers,
+    /// Additional sequences, they take precedence over built-in ones.
+    pub sequences: Vec<([char; 2], char)>,
+}
+impl Default for ComposeOptions {
+    /// Ctrl+K, same as digraphs in Vim.
+    fn default() -> Self {
+        Self {
+            key: KeyCode::Char('k'),
+            modifiers: KeyModifiers::CONTROL,
+            sequences: Vec::new(),
+        }
+    }
+}
+impl ComposeOptions {
+    pub(crate) fn matches(&self, key: &KeyEvent) -> bool {
+        key.code == self.key && key.modifiers == self.modifiers
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HintKind {
+    /// Replace text with correct answer up to first wrong char and one more char.
+    #[default]
+    NextLetter,
+    /// Show first letter of each word, e.g. `h____ w____`.
+    FirstLetters,
+    /// Show length of each word, e.g. `_____ _____`.
+    Length,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExplanationOptions {
+    /// Key showing and hiding explanations, it takes precedence over
+    /// [`EndPrompt`](crate::EndPrompt) keys.
+    pub key: KeyCode,
+    /// Explanations are hidden until the key is pressed.
+    pub hidden: bool,
+}
+impl Default for ExplanationOptions {
+    fn default() -> Self {
+        Self {
+            key: KeyCode::Char('e'),
+            hidden: false,
+        }
+    }
+}
+
+/// Settings of blank field, see [`Options::blanks`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlankOptions {
+    /// Text shown in empty field, e.g. `verb, past tense`.
+    pub placeholder: Option<String>,
+    /// Expected width in characters, shorter text is padded with `_`.
+    pub width: Option<usize>,
+    /// Maximal number of characters, further input is ignored.
+    pub max_length: Option<usize>,
+    /// Pattern the text is typed into: `#` is digit, `a` is letter, `*` is any char and other
+    /// chars are inserted automatically, e.g. `##.##.####` for dates.
+    ///
+    /// Incomplete text is reported when input finishes.
+    pub mask: Option<String>,
+    /// How typed text is displayed.
+    pub echo: Echo,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letter_shortcuts_skip_navigation_keys() {
+        let letters = OneOfPrefix::Letters;
+        assert_eq!(letters.shortcut('a'), Some(0));
+        assert_eq!(letters.shortcut('C'), Some(2));
+        assert_eq!(letters.shortcut('l'), Some(9));
+        assert_eq!(letters.shortcut('z'), Some(22));
+        assert_eq!(letters.shortcut('j'), None);
+        assert_eq!(letters.shortcut('K'), None);
+        assert_eq!(letters.shortcut('q'), None);
+        assert_eq!(letters.shortcut('é'), None);
+        let labels = Labels::default();
+        assert_eq!(letters.label(0, &labels), " a) ");
+        assert_eq!(letters.label(9, &labels), " l) ");
+        assert_eq!(letters.label(23, &labels), labels.bullet);
+    }
+
+    #[test]
+    fn number_shortcuts_up_to_nine() {
+        let numbers = OneOfPrefix::Numbers;
+        assert_eq!(numbers.shortcut('1'), Some(0));
+        assert_eq!(numbers.shortcut('9'), Some(8));
+        assert_eq!(numbers.shortcut('0'), None);
+        let labels = Labels::default();
+        assert_eq!(numbers.label(8, &labels), " 9. ");
+        assert_eq!(numbers.label(9, &labels), " 10. ");
+        assert_eq!(OneOfPrefix::Bullet.shortcut('1'), None);
+    }
+}
@@ -0,0 +1,66 @@
+use std::{collections::HashMap, fmt, rc::Rc};
+
+/// Values for single blank fields with optional default for the rest.
+pub(crate) struct PerBlank<T: ?Sized> {
+    default: Option<Rc<T>>,
+    blanks: HashMap<(usize, usize), Rc<T>>,
+}
+impl<T: ?Sized> Clone for PerBlank<T> {
+    fn clone(&self) -> Self {
+        Self {
+            default: self.default.clone(),
+            blanks: self.blanks.clone(),
+        }
+    }
+}
+impl<T: ?Sized> Default for PerBlank<T> {
+    fn default() -> Self {
+        Self {
+            default: None,
+            blanks: HashMap::new(),
+        }
+    }
+}
+impl<T: ?Sized> fmt::Debug for PerBlank<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PerBlank")
+            .field("default", &self.default.is_some())
+            .field("blanks", &self.blanks.keys())
+            .finish()
+    }
+}
+impl<T: ?Sized> PerBlank<T> {
+    pub(crate) fn new(default: Rc<T>) -> Self {
+        Self {
+            default: Some(default),
+            blanks: HashMap::new(),
+        }
+    }
+    /// `block` and `blank` are indexes as in [`Options::blanks`](crate::Options::blanks).
+    pub(crate) fn with_blank(mut self, block: usize, blank: usize, value: Rc<T>) -> Self {
+        self.blanks.insert((block, blank), value);
+        self
+    }
+    /// Value for blank field or default.
+    pub(crate) fn get(&self, block: usize, blank: usize) -> Option<&T> {
+        self.blanks
+            .get(&(block, blank))
+            .or(self.default.as_ref())
+            .map(AsRef::as_ref)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_overrides_default() {
+        let values = PerBlank::<str>::new("default".into()).with_blank(1, 0, "blank".into());
+        assert_eq!(values.get(1, 0), Some("blank"));
+        assert_eq!(values.get(0, 1), Some("default"));
+        let values = PerBlank::<str>::default().with_blank(0, 0, "blank".into());
+        assert_eq!(values.get(0, 1), None);
+        assert_eq!(values.clone().get(0, 0), Some("blank"));
+    }
+}
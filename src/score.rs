@@ -0,0 +1,251 @@
+use crate::{
+    answer_matcher::{edit_distance, matches_any, AnswerMatcher},
+    labels::{self, Labels},
+    AcceptedAnswers, Options,
+};
+
+/// Result of checking response against correct answers.
+///
+/// Every blank field and every `one of`, `any of` and `order` block is one item worth one point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    pub blocks: Vec<BlockScore>,
+}
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockScore {
+    /// Sum of credit for all items, from `0.0` to [`items`](Self::items).
+    pub points: f64,
+    /// Number of fully correct items.
+    pub correct: usize,
+    pub items: usize,
+}
+impl Score {
+    pub fn points(&self) -> f64 {
+        self.blocks.iter().map(|x| x.points).sum()
+    }
+    pub fn correct(&self) -> usize {
+        self.blocks.iter().map(|x| x.correct).sum()
+    }
+    pub fn items(&self) -> usize {
+        self.blocks.iter().map(|x| x.items).sum()
+    }
+    /// Points in percents, `100.0` if there is no items.
+    pub fn percent(&self) -> f64 {
+        let items = self.items();
+        if items == 0 {
+            100.0
+        } else {
+            self.points() / items as f64 * 100.0
+        }
+    }
+    /// e.g. "7/9 correct, 78%"
+    pub fn summary(&self) -> String {
+        self.summary_with(&Labels::default())
+    }
+    /// Same as [`summary`](Self::summary), but with [`Labels::summary`] from `labels`.
+    pub fn summary_with(&self, labels: &Labels) -> String {
+        let percent = format!("{:.0}", self.percent());
+        labels::fill(&labels.summary, &[&self.correct(), &self.items(), &percent])
+    }
+}
+impl BlockScore {
+    fn add(&mut self, credit: f64) {
+        self.points += credit;
+        self.items += 1;
+        if credit >= 1.0 {
+            self.correct += 1;
+        }
+    }
+}
+
+/// Scores `user_answer` in the same way as [`display_answer_with`](crate::display_answer_with)
+/// checks it.
+///
+/// `any of` and `order` blocks get partial credit, blank fields get it only with
+/// [`Options::fuzzy_credit`].
+pub fn score(
+    input_blocks: &s_text_input_f::Blocks,
+    user_answer: &s_text_input_f::Response,
+    correct_answer: &AcceptedAnswers,
+    options: &Options,
+) -> Score {
+    let blocks = input_blocks
+        .iter()
+        .zip(user_answer.iter().zip(correct_answer))
+        .enumerate()
+        .map(|(i, (block, (user_answer, correct_answer)))| {
+            let mut block_score = BlockScore::default();
+            match block {
+                s_text_input_f::Block::Order(_) => {
+                    block_score.add(order_credit(user_answer, correct_answer));
+                }
+                s_text_input_f::Block::AnyOf(_) => {
+                    block_score.add(any_of_credit(user_answer, correct_answer));
+                }
+                s_text_input_f::Block::OneOf(_) => {
+                    let user_answer = s_text_input_f::response_as_one_of(user_answer.clone())
+                        .and_then(|x| x.ok());
+                    let is_correct = user_answer.is_some_and(|user_answer| {
+                        correct_answer
+                            .iter()
+                            .flatten()
+                            .any(|x| x.parse() == Ok(user_answer))
+                    });
+                    block_score.add(if is_correct { 1.0 } else { 0.0 });
+                }
+                s_text_input_f::Block::Paragraph(_) => {
+                    for (blank, accepted) in correct_answer.iter().enumerate() {
+                        let user_answer = user_answer.get(blank).map_or("", String::as_str);
+                        block_score.add(blank_credit(
+                            options.matchers.get(i, blank),
+                            user_answer,
+                            accepted,
+                            options.fuzzy_credit,
+                        ));
+                    }
+                }
+                _ => (),
+            }
+            block_score
+        })
+        .collect();
+    Score { blocks }
+}
+
+fn blank_credit(
+    matcher: &dyn AnswerMatcher,
+    user_answer: &str,
+    accepted: &[String],
+    fuzzy_credit: bool,
+) -> f64 {
+    if matches_any(matcher, user_answer, accepted) {
+        1.0
+    } else if fuzzy_credit {
+        let user_answer = user_answer.trim();
+        accepted
+            .iter()
+            .map(|x| {
+                let x = x.trim();
+                let len = user_answer.chars().count().max(x.chars().count());
+                if len == 0 {
+                    1.0
+                } else {
+                    1.0 - edit_distance(user_answer, x) as f64 / len as f64
+                }
+            })
+            .fold(0.0, f64::max)
+    } else {
+        0.0
+    }
+}
+
+/// Share of items on correct positions.
+fn order_credit(user_answer: &[String], correct_answer: &[Vec<String>]) -> f64 {
+    if correct_answer.is_empty() {
+        return 1.0;
+    }
+    let on_place = user_answer
+        .iter()
+        .zip(correct_answer)
+        .filter(|(user_answer, accepted)| accepted.contains(user_answer))
+        .count();
+    on_place as f64 / correct_answer.len() as f64
+}
+
+/// Share of correct items selected, minus wrongly selected ones.
+fn any_of_credit(user_answer: &[String], correct_answer: &[Vec<String>]) -> f64 {
+    let correct = correct_answer
+        .iter()
+        .filter_map(|x| x.first())
+        .collect::<Vec<_>>();
+    let right = user_answer.iter().filter(|x| correct.contains(x)).count();
+    let wrong = user_answer.len() - right;
+    if correct.is_empty() {
+        if wrong == 0 {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        (right as f64 - wrong as f64).max(0.0) / correct.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer_matcher::Exact;
+    use s_text_input_f::{Block, ParagraphItem};
+
+    fn strings(x: &[&str]) -> Vec<String> {
+        x.iter().map(|&x| x.to_owned()).collect()
+    }
+    fn accepted(x: &[&str]) -> Vec<Vec<String>> {
+        x.iter().map(|&x| vec![x.to_owned()]).collect()
+    }
+
+    #[test]
+    fn score_of_mixed_blocks() {
+        let blocks = vec![
+            Block::OneOf(strings(&["a", "b"])),
+            Block::Paragraph(vec![ParagraphItem::Placeholder; 6]),
+            Block::AnyOf(strings(&["a", "b", "c"])),
+            Block::Order(strings(&["a", "b"])),
+        ];
+        let user_answer = vec![
+            strings(&["1"]),
+            strings(&["a", "b", "c", "d", "e", "x"]),
+            strings(&["2", "0"]),
+            strings(&["1", "0"]),
+        ];
+        let correct_answer = vec![
+            accepted(&["1"]),
+            accepted(&["a", "b", "c", "d", "e", "f"]),
+            accepted(&["0", "2"]),
+            accepted(&["0", "1"]),
+        ];
+        let score = score(&blocks, &user_answer, &correct_answer, &Options::default());
+        assert_eq!(score.correct(), 7);
+        assert_eq!(score.items(), 9);
+        assert_eq!(score.points(), 7.0);
+        assert_eq!(score.summary(), "7/9 correct, 78%");
+        let labels = Labels {
+            summary: "{2}% ({0}/{1})".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(score.summary_with(&labels), "78% (7/9)");
+        assert_eq!(Score { blocks: vec![] }.percent(), 100.0);
+    }
+
+    #[test]
+    fn blank_credit_is_fuzzy_only_if_enabled() {
+        let accepted = strings(&["color", "colour"]);
+        assert_eq!(blank_credit(&Exact, " colour ", &accepted, false), 1.0);
+        assert_eq!(blank_credit(&Exact, "colr", &accepted, false), 0.0);
+        assert_eq!(blank_credit(&Exact, "colr", &accepted, true), 0.8);
+        assert_eq!(blank_credit(&Exact, "", &strings(&[""]), true), 1.0);
+    }
+
+    #[test]
+    fn order_credit_counts_items_on_place() {
+        let correct = accepted(&["0", "1", "2"]);
+        assert_eq!(order_credit(&strings(&["0", "1", "2"]), &correct), 1.0);
+        assert_eq!(
+            order_credit(&strings(&["0", "2", "1"]), &correct),
+            1.0 / 3.0
+        );
+        assert_eq!(order_credit(&strings(&["0"]), &correct), 1.0 / 3.0);
+        assert_eq!(order_credit(&[], &[]), 1.0);
+    }
+
+    #[test]
+    fn any_of_credit_subtracts_wrong_items() {
+        let correct = accepted(&["0", "2"]);
+        assert_eq!(any_of_credit(&strings(&["2", "0"]), &correct), 1.0);
+        assert_eq!(any_of_credit(&strings(&["0"]), &correct), 0.5);
+        assert_eq!(any_of_credit(&strings(&["0", "1"]), &correct), 0.0);
+        assert_eq!(any_of_credit(&strings(&["1", "3", "0"]), &correct), 0.0);
+        assert_eq!(any_of_credit(&[], &[]), 1.0);
+        assert_eq!(any_of_credit(&strings(&["1"]), &[]), 0.0);
+    }
+}
@@ -0,0 +1,164 @@
+use ratatui::style::{Color, Modifier, Style, Stylize};
+
+/// Styles for every visual role, see [`Options::theme`](crate::Options::theme).
+///
+/// Default theme is made for dark terminals, see also [`light`](Self::light),
+/// [`high_contrast`](Self::high_contrast) and [`accessible`](Self::accessible).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Focused item of `one of` block.
+    pub focused: Style,
+    /// Selected item of `one of` block.
+    pub selected: Style,
+    /// Items of `one of` block.
+    pub item: Style,
+    /// Prefix of `one of` items during input.
+    pub bullet: Style,
+    /// Prefix of `one of` items in answered blocks.
+    pub answered_bullet: Style,
+    /// Empty blank field and missing answers.
+    pub empty_blank: Style,
+    /// Filled blank field which is not focused.
+    pub filled_blank: Style,
+    /// Text of focused blank field.
+    pub active_blank: Style,
+    pub cursor: Style,
+    /// Text typed in [`get_text_input`](crate::get_text_input) and similar inputs.
+    pub text_input: Style,
+    /// Correct answers and correct parts of answers.
+    pub correct: Style,
+    /// Wrongly selected items.
+    pub incorrect: Style,
+    /// Wrong answers for blank fields and wrong parts of them.
+    pub incorrect_text: Style,
+    /// Correct answer displayed instead of wrong one.
+    pub correction: Style,
+    /// Parts of correct answer missing in user answer.
+    pub missing: Style,
+    /// Secondary text: hints, other accepted answers, explanations and countdown.
+    pub dimmed: Style,
+    /// Prompts and notes, e.g. [`EndPrompt`](crate::EndPrompt) text.
+    pub prompt: Style,
+    /// Countdown when time is almost out and errors of blank fields.
+    pub warning: Style,
+    /// Suggestions under focused blank field.
+    pub suggestion: Style,
+    /// Focused suggestion.
+    pub focused_suggestion: Style,
+    /// Mark answers with `✓`, `✗` and `->`, so they can be told apart without colors.
+    pub markers: bool,
+}
+
+pub(crate) const CORRECT_MARKER: &str = "✓";
+pub(crate) const INCORRECT_MARKER: &str = "✗";
+pub(crate) const CORRECTION_ARROW: &str = " -> ";
+impl Default for Theme {
+    /// [`accessible`](Self::accessible) theme if `NO_COLOR` environment variable is set and not
+    /// empty.
+    fn default() -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|x| !x.is_empty()) {
+            return Self::accessible();
+        }
+        Self::colored()
+    }
+}
+impl Theme {
+    /// Default theme regardless of `NO_COLOR`.
+    pub fn colored() -> Self {
+        Self {
+            focused: Style::new().bold().magenta(),
+            selected: Style::new().bold().not_italic(),
+            item: Style::new().italic(),
+            bullet: Style::new().blue(),
+            answered_bullet: Style::new().white(),
+            empty_blank: Style::new().dark_gray().italic(),
+            filled_blank: Style::new().underlined().gray().italic(),
+            active_blank: Style::new().underlined().italic(),
+            cursor: Style::new().blue(),
+            text_input: Style::new().italic(),
+            correct: Style::new().green(),
+            incorrect: Style::new().red(),
+            incorrect_text: Style::new().red().crossed_out(),
+            correction: Style::new().yellow(),
+            missing: Style::new().yellow().underlined(),
+            dimmed: Style::new().dark_gray(),
+            prompt: Style::new().dark_gray().italic(),
+            warning: Style::new().red(),
+            suggestion: Style::new().white().on_dark_gray(),
+            focused_suggestion: Style::new().bold().magenta().on_dark_gray(),
+            markers: false,
+        }
+    }
+    /// Theme for terminals with light background.
+    pub fn light() -> Self {
+        Self {
+            answered_bullet: Style::new().fg(Color::Reset),
+            filled_blank: Style::new().underlined().fg(Color::Reset).italic(),
+            cursor: Style::new().magenta(),
+            correction: Style::new().blue(),
+            missing: Style::new().blue().underlined(),
+            suggestion: Style::new().black().on_gray(),
+            focused_suggestion: Style::new().bold().magenta().on_gray(),
+            ..Self::colored()
+        }
+    }
+    /// Bright colors and modifiers instead of subtle shades.
+    pub fn high_contrast() -> Self {
+        Self {
+            focused: Style::new().bold().add_modifier(Modifier::REVERSED),
+            selected: Style::new().bold().underlined().not_italic(),
+            item: Style::new(),
+            bullet: Style::new().light_cyan().bold(),
+            answered_bullet: Style::new().white().bold(),
+            empty_blank: Style::new().white().italic(),
+            filled_blank: Style::new().underlined().white().bold(),
+            active_blank: Style::new().underlined().bold(),
+            cursor: Style::new().add_modifier(Modifier::REVERSED),
+            text_input: Style::new(),
+            correct: Style::new().light_green().bold(),
+            incorrect: Style::new().light_red().bold(),
+            incorrect_text: Style::new().light_red().bold().crossed_out(),
+            correction: Style::new().light_yellow().bold(),
+            missing: Style::new().light_yellow().bold().underlined(),
+            dimmed: Style::new().white(),
+            prompt: Style::new().white().italic(),
+            warning: Style::new()
+                .light_red()
+                .bold()
+                .add_modifier(Modifier::REVERSED),
+            suggestion: Style::new().add_modifier(Modifier::REVERSED),
+            focused_suggestion: Style::new()
+                .light_cyan()
+                .bold()
+                .add_modifier(Modifier::REVERSED),
+            markers: false,
+        }
+    }
+    /// No colors, only modifiers and [`markers`](Self::markers), e.g. for colour-blind users and
+    /// monochrome terminals.
+    pub fn accessible() -> Self {
+        Self {
+            focused: Style::new().bold().add_modifier(Modifier::REVERSED),
+            selected: Style::new().bold().underlined().not_italic(),
+            item: Style::new(),
+            bullet: Style::new(),
+            answered_bullet: Style::new(),
+            empty_blank: Style::new().dim().italic(),
+            filled_blank: Style::new().underlined().italic(),
+            active_blank: Style::new().underlined(),
+            cursor: Style::new().add_modifier(Modifier::REVERSED),
+            text_input: Style::new(),
+            correct: Style::new().bold(),
+            incorrect: Style::new().crossed_out(),
+            incorrect_text: Style::new().crossed_out(),
+            correction: Style::new().bold(),
+            missing: Style::new().bold().underlined(),
+            dimmed: Style::new().dim(),
+            prompt: Style::new().dim().italic(),
+            warning: Style::new().bold().add_modifier(Modifier::REVERSED),
+            suggestion: Style::new().add_modifier(Modifier::REVERSED),
+            focused_suggestion: Style::new().bold().add_modifier(Modifier::REVERSED),
+            markers: true,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::{Duration, Instant};
+
+use ratatui::text::Line;
+
+use crate::{labels, Options};
+
+pub(crate) enum TimedEvent {
+    Event(crossterm::event::Event),
+    /// Displayed time left changed.
+    Tick,
+    TimedOut,
+}
+
+/// Reads terminal event, waiting not longer than until `deadline`.
+///
+/// Wakes up with [`TimedEvent::Tick`] every time whole second passes, so countdown can be redrawn.
+pub(crate) fn read(deadline: Option<Instant>) -> std::io::Result<TimedEvent> {
+    let Some(deadline) = deadline else {
+        return crossterm::event::read().map(TimedEvent::Event);
+    };
+    let time_left = deadline.saturating_duration_since(Instant::now());
+    if time_left.is_zero() {
+        return Ok(TimedEvent::TimedOut);
+    }
+    let until_tick = match Duration::from_nanos(time_left.subsec_nanos().into()) {
+        x if x.is_zero() => Duration::from_secs(1),
+        x => x,
+    };
+    if crossterm::event::poll(until_tick)? {
+        crossterm::event::read().map(TimedEvent::Event)
+    } else {
+        Ok(TimedEvent::Tick)
+    }
+}
+
+/// Status line with time left until `deadline`, e.g. `Time left: 1:05`.
+pub(crate) fn countdown_line(deadline: Instant, options: &Options) -> Line<'static> {
+    let time_left = deadline.saturating_duration_since(Instant::now());
+    // round up, so it shows 0:00 only when time is out
+    let secs = time_left.as_secs() + u64::from(time_left.subsec_nanos() > 0);
+    let style = if secs <= 10 {
+        options.theme.warning
+    } else {
+        options.theme.dimmed
+    };
+    let time_left = format!("{}:{:02}", secs / 60, secs % 60);
+    Line::styled(
+        labels::fill(&options.labels.time_left, &[&time_left]),
+        style,
+    )
+}
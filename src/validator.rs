@@ -0,0 +1,176 @@
+use std::rc::Rc;
+
+use crate::{labels::fill, per_blank::PerBlank, Labels};
+
+/// Checks text of blank field before input is finished, see [`Options::validators`](crate::Options::validators).
+///
+/// Empty fields are not checked, use
+/// [`Options::require_all_answered`](crate::Options::require_all_answered) for them.
+///
+/// Implemented for closures returning error message, e.g. to check text is one of words:
+/// ```
+/// let validator = |text: &str| match text.trim() {
+///     "yes" | "no" => Ok(()),
+///     _ => Err("Enter yes or no.".to_owned()),
+/// };
+/// let validators = ratatui_inputs::validator::Validators::default().with_blank(0, 0, validator);
+/// ```
+pub trait Validator {
+    /// # Errors
+    /// message displayed next to field if `text` is invalid
+    fn validate(&self, text: &str, labels: &Labels) -> Result<(), String>;
+}
+impl<F: Fn(&str) -> Result<(), String>> Validator for F {
+    fn validate(&self, text: &str, _: &Labels) -> Result<(), String> {
+        self(text)
+    }
+}
+
+/// Any number, e.g. `-1.5`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Numeric;
+impl Validator for Numeric {
+    fn validate(&self, text: &str, labels: &Labels) -> Result<(), String> {
+        match text.trim().parse::<f64>() {
+            Ok(x) if x.is_finite() => Ok(()),
+            _ => Err(labels.not_a_number.clone()),
+        }
+    }
+}
+
+/// Whole number from `min` to `max` inclusive.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntRange {
+    pub min: i64,
+    pub max: i64,
+}
+impl Validator for IntRange {
+    fn validate(&self, text: &str, labels: &Labels) -> Result<(), String> {
+        match text.trim().parse::<i64>() {
+            Ok(x) if (self.min..=self.max).contains(&x) => Ok(()),
+            _ => Err(fill(&labels.not_in_range, &[&self.min, &self.max])),
+        }
+    }
+}
+
+/// At most `max` chars after trimming.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxLength {
+    pub max: usize,
+}
+impl Validator for MaxLength {
+    fn validate(&self, text: &str, labels: &Labels) -> Result<(), String> {
+        if text.trim().chars().count() <= self.max {
+            Ok(())
+        } else {
+            Err(fill(&labels.too_long, &[&self.max]))
+        }
+    }
+}
+
+/// One word without whitespace.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SingleWord;
+impl Validator for SingleWord {
+    fn validate(&self, text: &str, labels: &Labels) -> Result<(), String> {
+        if text.split_whitespace().count() <= 1 {
+            Ok(())
+        } else {
+            Err(labels.not_single_word.clone())
+        }
+    }
+}
+
+/// Trimmed text matches regular expression as a whole.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+pub struct Regex {
+    regex: regex::Regex,
+    /// Error instead of [`Labels::not_matching`], e.g. `Enter date as YYYY-MM-DD.`.
+    pub message: Option<String>,
+}
+#[cfg(feature = "regex")]
+impl Regex {
+    /// # Errors
+    /// if `pattern` is invalid
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            regex: regex::Regex::new(&format!("^(?:{pattern})$"))?,
+            message: None,
+        })
+    }
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+#[cfg(feature = "regex")]
+impl Validator for Regex {
+    fn validate(&self, text: &str, labels: &Labels) -> Result<(), String> {
+        if self.regex.is_match(text.trim()) {
+            Ok(())
+        } else {
+            Err(self
+                .message
+                .clone()
+                .unwrap_or_else(|| labels.not_matching.clone()))
+        }
+    }
+}
+
+/// [`Validator`]s for single blank fields.
+#[derive(Debug, Clone, Default)]
+pub struct Validators(PerBlank<dyn Validator>);
+impl Validators {
+    /// Checks `blank`-th blank field of `block`-th block with `validator`.
+    pub fn with_blank(
+        self,
+        block: usize,
+        blank: usize,
+        validator: impl Validator + 'static,
+    ) -> Self {
+        Self(self.0.with_blank(block, blank, Rc::new(validator)))
+    }
+    pub fn get(&self, block: usize, blank: usize) -> Option<&dyn Validator> {
+        self.0.get(block, blank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_validators() {
+        let labels = Labels::default();
+        assert!(Numeric.validate(" -1.5 ", &labels).is_ok());
+        assert!(Numeric.validate("inf", &labels).is_err());
+        let range = IntRange { min: 1, max: 10 };
+        assert!(range.validate("10", &labels).is_ok());
+        assert_eq!(
+            range.validate("11", &labels),
+            Err("Enter a whole number from 1 to 10.".to_owned())
+        );
+        assert!(MaxLength { max: 3 }.validate(" été ", &labels).is_ok());
+        assert!(SingleWord.validate("two words", &labels).is_err());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_matches_whole_text() {
+        let labels = Labels::default();
+        let date = Regex::new(r"\d{4}-\d{2}-\d{2}").unwrap();
+        assert!(date.validate(" 2024-01-31 ", &labels).is_ok());
+        assert_eq!(
+            date.validate("2024-01-31T", &labels),
+            Err(labels.not_matching.clone())
+        );
+        let date = date.with_message("Enter date as YYYY-MM-DD.");
+        assert_eq!(
+            date.validate("31.01.2024", &labels),
+            Err("Enter date as YYYY-MM-DD.".to_owned())
+        );
+        assert!(Regex::new("a|ab").unwrap().validate("ab", &labels).is_ok());
+        assert!(Regex::new("(").is_err());
+    }
+}